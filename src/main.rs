@@ -1,35 +1,237 @@
-use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike};
 use eframe::egui;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
-use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
-use std::sync::mpsc;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, mpsc};
 use std::thread;
+use std::time::Instant;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct FavoriteCommand {
+    /// Stable key for per-favorite data such as the line cache
+    #[serde(default)]
+    id: u64,
     name: String,
     command: String,
+    /// Filters restored when the favorite is used; `None` leaves them as they are
+    #[serde(default)]
+    filters: Option<FavoriteFilters>,
+}
+
+/// Remote host the command runs on over `ssh`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct SshTarget {
+    host: String,
+    /// Login name; empty leaves it to the ssh config
+    user: String,
+    port: u16,
+}
+
+impl Default for SshTarget {
+    fn default() -> Self {
+        Self {
+            host: String::new(),
+            user: String::new(),
+            port: 22,
+        }
+    }
+}
+
+/// State of the Docker container picker window
+#[derive(Default)]
+struct DockerPicker {
+    /// `docker ps` output on its way back
+    pending: Option<mpsc::Receiver<Result<String, String>>>,
+    /// Running containers as (id, name)
+    containers: Vec<(String, String)>,
+    selected: Option<usize>,
+}
+
+/// State of the Kubernetes pod picker window
+#[derive(Default)]
+struct PodPicker {
+    /// Empty uses the current context's namespace
+    namespace: String,
+    /// The namespace was edited since pods were last loaded
+    namespace_edited: bool,
+    /// `kubectl get pods` output on its way back
+    pending_pods: Option<mpsc::Receiver<Result<String, String>>>,
+    pods: Vec<String>,
+    selected_pod: Option<usize>,
+    /// Container names of the selected pod on their way back
+    pending_containers: Option<mpsc::Receiver<Result<String, String>>>,
+    containers: Vec<String>,
+    /// `None` leaves the choice to kubectl's default container
+    selected_container: Option<usize>,
+}
+
+/// Exit code ssh reports for its own failures, such as a refused or dropped connection
+const SSH_CONNECTION_FAILED: i32 = 255;
+
+/// Filter state saved alongside a favorite command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FavoriteFilters {
+    selected_log_levels: HashSet<String>,
+    filter_mode: FilterMode,
+    search_text: String,
+    search_scope: SearchScope,
+    time_filter: TimeFilter,
+}
+
+/// The time filter selection. Custom ranges are stored as absolute date
+/// components; relative and predefined spans are recomputed against now.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct TimeFilter {
+    mode: TimeSpanMode,
+    custom_from_year: i32,
+    custom_from_month: u32,
+    custom_from_day: u32,
+    custom_from_hour: u32,
+    custom_from_minute: u32,
+    custom_to_year: i32,
+    custom_to_month: u32,
+    custom_to_day: u32,
+    custom_to_hour: u32,
+    custom_to_minute: u32,
+    relative_amount: i32,
+    relative_unit: TimeUnit,
+    /// Keep entries outside the range instead of inside it
+    invert: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 struct Settings {
+    /// Schema version the file was written with; see `migrate_settings`
+    version: u32,
     log_command: String,
     refresh_interval: u64,
     favorite_commands: Vec<FavoriteCommand>,
+    /// Wipe the log buffer whenever collection is restarted
+    clear_on_restart: bool,
+    /// Structured field used to suppress repeats; empty disables deduplication
+    dedup_field: String,
+    dedup_window_secs: i64,
+    max_log_entries: usize,
+    time_separator: TimeSeparator,
+    /// Warn when a running stream is silent this long; 0 disables the warning
+    stale_after_secs: u64,
+    /// Window size at last exit, restored on startup
+    window_size: Option<[f32; 2]>,
+    striped_rows: bool,
+    /// Stripe tint for the log grid; `None` uses the theme default
+    stripe_color: Option<[u8; 3]>,
+    /// Regexes whose named capture groups become extra columns
+    extraction_rules: Vec<String>,
+    /// Drop lines that are empty or whitespace-only at ingest
+    skip_blank_lines: bool,
+    time_filter: TimeFilter,
+    /// Last value entered for each `{name}` command placeholder
+    placeholder_values: HashMap<String, String>,
+    /// Drop entries received longer ago than this, alongside the count cap
+    max_entry_age_secs: Option<u64>,
+    show_level_column: bool,
+    theme: Theme,
+    /// Color severity chips; `None` follows the `NO_COLOR` environment variable
+    colorize_levels: Option<bool>,
+    /// Render the timestamp column as time elapsed ("3m ago")
+    relative_timestamps: bool,
+    /// Keep only one in this many lines at ingest; 1 keeps everything
+    sample_every: u32,
+    /// Levels that are always kept while sampling
+    sample_keep_levels: Vec<String>,
+    /// chrono format used to render the timestamp column
+    timestamp_display_format: String,
+    /// Command run from a row's context menu, e.g. `mytool investigate {content}`
+    row_action_command: String,
+    /// Show the row action's stdout in a toast when it finishes
+    show_row_action_output: bool,
+    /// How command and file output bytes are decoded into text
+    encoding: LogEncoding,
+    /// Regex with a `level` named group; when set it replaces keyword detection
+    level_pattern: String,
+    /// Show lines verbatim, skipping timestamp extraction and field parsing
+    raw_mode: bool,
+    /// Lines cached per favorite and shown while it starts; 0 disables the cache
+    favorite_cache_lines: usize,
+    /// Alert when more error lines than this arrive within the burst window; 0 disables
+    burst_threshold: usize,
+    burst_window_secs: u64,
+    /// Also show a toast when a burst starts
+    burst_notify: bool,
+    syslog_protocol: SyslogProtocol,
+    syslog_port: u16,
+    /// Regex whose first capture group routes lines into side-by-side streams; empty disables
+    split_pattern: String,
+    /// Lines longer than this many bytes are cut at ingest; 0 keeps them whole
+    max_line_bytes: usize,
+    /// Hide the favorites list behind its search box beyond this many; 0 always lists them
+    favorites_search_first_above: usize,
+    /// History lines requested from journalctl/docker/kubectl followers on start; 0 disables
+    backlog_lines: usize,
+    /// Action name (see `KEY_ACTIONS`) to key combo; missing actions use the default
+    keybindings: HashMap<String, String>,
+    /// Parse the first whitespace-delimited token with `first_token_format`
+    /// before trying the generic timestamp patterns
+    first_token_timestamp: bool,
+    first_token_format: String,
+    /// Sound/flash cues keyed by `CUE_LEVELS` name
+    level_cues: HashMap<String, LevelCue>,
+    /// Silence every cue without losing the configuration
+    cues_muted: bool,
+    /// Display lines in timestamp order instead of arrival order
+    sort_by_timestamp: bool,
+    /// Clear the buffer after this many minutes without new lines; 0 disables
+    auto_clear_idle_mins: u64,
+    /// Stop showing the loading spinner this long after a successful spawn,
+    /// even without output; 0 waits for the first line
+    loading_timeout_secs: u64,
+    /// Ordered text color rules; the first match colors the row
+    color_rules: Vec<ColorRule>,
+    /// Run the command through `sh -c` (`cmd /C` on Windows) so pipes and
+    /// redirects work, instead of splitting it on whitespace
+    use_shell: bool,
+    /// Environment variables set for the command, e.g. `KUBECONFIG`
+    env: Vec<(String, String)>,
+    /// Directory the command runs in; `None` inherits the app's
+    working_dir: Option<PathBuf>,
+    /// Run the command again when it exits on its own
+    auto_restart: bool,
+    /// Delay before the first automatic restart; doubles on each quick exit
+    restart_backoff_secs: u64,
+    /// Each tab keeps its own levels, search and time filter instead of sharing them
+    per_tab_filters: bool,
+    /// Treat the command field as a file path and follow it without spawning `tail`
+    follow_file: bool,
+    /// Run the command on this host over ssh; `None` runs it locally
+    ssh: Option<SshTarget>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 enum FilterMode {
     IncludeSelected,
     ExcludeSelected,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum SearchScope {
+    Content,
+    Timestamp,
+    Both,
+    /// The line exactly as received, before the timestamp was cut out
+    RawLine,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 enum TimeSpanMode {
     Disabled,
     Predefined(PredefinedSpan),
@@ -37,7 +239,7 @@ enum TimeSpanMode {
     Relative,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 enum PredefinedSpan {
     Last15Minutes,
     Last30Minutes,
@@ -49,7 +251,220 @@ enum PredefinedSpan {
     Last1Month,
 }
 
+/// Granularity of the separator rows inserted between log entries
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum TimeSeparator {
+    Off,
+    Hourly,
+    Daily,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum Theme {
+    Dark,
+    Light,
+    HighContrast,
+}
+
 #[derive(Debug, Clone, PartialEq)]
+enum CollectionState {
+    Tailing,
+    Paused,
+    Stopped,
+    Error(String),
+}
+
+/// A log source shown in its own tab, with everything that belongs to its
+/// collection. `LogsApp::tabs[active_tab]` is the one on screen.
+struct LogSource {
+    command: String,
+    /// Follow `command` as a file path instead of running it
+    follow_file: bool,
+    /// Run `command` on this host over ssh
+    ssh: Option<SshTarget>,
+    logs: Vec<LogEntry>,
+    log_receiver: Option<mpsc::Receiver<CollectorMessage>>,
+    log_thread_handle: Option<thread::JoinHandle<()>>,
+    /// The running command, shared with the collection thread so it can be killed
+    log_child: Option<Arc<Mutex<Option<Child>>>>,
+    /// Raised to stop collection threads: socket listeners poll it while
+    /// waiting for traffic, and the command thread checks it before handing
+    /// over its child so a stop can't miss a child that was just spawned
+    collection_stop: Arc<AtomicBool>,
+    /// Command line the collection thread was started with
+    running_command: Option<String>,
+    /// Why the last command couldn't be started, shown in the log panel
+    last_error: Option<String>,
+    /// Collecting lines piped into the app instead of running the command
+    reading_stdin: bool,
+    is_loading: bool,
+    collection_state: CollectionState,
+    /// Bytes read and total size of the file being imported
+    import_progress: Option<(u64, u64)>,
+    last_log_arrival: Option<Instant>,
+    collection_started: Option<Instant>,
+    /// When the command thread reported a successful spawn
+    spawned_at: Option<Instant>,
+    /// State of the spawned command; `None` when no command was started, or
+    /// it was stopped from the UI
+    process_status: Option<ProcessStatus>,
+    /// When an automatic restart is due after the command exited
+    restart_at: Option<Instant>,
+    /// Delay before the next automatic restart, in seconds; 0 until one happens
+    restart_backoff: u64,
+    /// Raw stdout bytes read by the collection thread, complete lines or not
+    bytes_received: Arc<AtomicU64>,
+    /// Lines dropped from the front of the buffer by the entry cap
+    trimmed_lines: u64,
+    /// Lines seen by the sampler since collection started
+    sampled_lines: u64,
+    /// Buffer wiped by "Clear Logs", restorable until the next clear or restart
+    cleared_logs: Option<Vec<LogEntry>>,
+    /// The buffer was auto-cleared and no line has arrived since
+    idle_cleared: bool,
+    /// Favorite whose command is running, for the line cache
+    active_favorite: Option<u64>,
+    /// Cached lines shown greyed out until the favorite's first fresh line
+    cached_preview: Vec<String>,
+    /// Arrival times of recent error lines, oldest first
+    error_arrivals: VecDeque<Instant>,
+    in_error_burst: bool,
+    /// `CUE_LEVELS` entries seen since the cues were last checked
+    new_cue_groups: [bool; 3],
+    /// Lines left by the filters on the last frame, for the status line
+    visible_count: usize,
+    /// Filters to restore when the tab is activated, with per-tab filters on
+    filters: Option<FavoriteFilters>,
+}
+
+impl LogSource {
+    fn new(command: String, follow_file: bool, ssh: Option<SshTarget>) -> Self {
+        Self {
+            command,
+            follow_file,
+            ssh,
+            logs: Vec::new(),
+            log_receiver: None,
+            log_thread_handle: None,
+            log_child: None,
+            collection_stop: Arc::new(AtomicBool::new(false)),
+            running_command: None,
+            last_error: None,
+            reading_stdin: false,
+            is_loading: false,
+            collection_state: CollectionState::Stopped,
+            import_progress: None,
+            last_log_arrival: None,
+            collection_started: None,
+            spawned_at: None,
+            process_status: None,
+            restart_at: None,
+            restart_backoff: 0,
+            bytes_received: Arc::new(AtomicU64::new(0)),
+            trimmed_lines: 0,
+            sampled_lines: 0,
+            cleared_logs: None,
+            idle_cleared: false,
+            active_favorite: None,
+            cached_preview: Vec::new(),
+            error_arrivals: VecDeque::new(),
+            in_error_burst: false,
+            new_cue_groups: [false; 3],
+            visible_count: 0,
+            filters: None,
+        }
+    }
+
+    /// Take the running command away from the collection thread, telling the
+    /// thread to stop
+    fn take_child(&mut self) -> Option<Child> {
+        self.collection_stop.store(true, Ordering::Relaxed);
+        self.log_child
+            .take()
+            .and_then(|shared| shared.lock().ok().and_then(|mut slot| slot.take()))
+    }
+
+    fn prune_error_arrivals(&mut self, now: Instant, window: std::time::Duration) {
+        while self
+            .error_arrivals
+            .front()
+            .is_some_and(|&arrived| now.duration_since(arrived) > window)
+        {
+            self.error_arrivals.pop_front();
+        }
+    }
+
+    /// Stop collecting, terminating the running command, if any, without
+    /// blocking the UI for the grace period. Its exit closes stdout, so the
+    /// reader thread sees EOF.
+    fn stop(&mut self) {
+        if let Some(child) = self.take_child() {
+            thread::spawn(move || LogsApp::terminate_child(child));
+        }
+        // Piped input can't be read again once its reader is gone
+        self.reading_stdin = false;
+        self.process_status = None;
+        self.restart_at = None;
+        self.log_receiver = None;
+        self.import_progress = None;
+        self.collection_state = CollectionState::Stopped;
+        if let Some(handle) = self.log_thread_handle.take() {
+            // Don't block the UI - let the thread finish naturally
+            std::mem::drop(handle);
+        }
+    }
+}
+
+/// What became of the spawned command, as reported by the collection thread
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ProcessStatus {
+    Running,
+    /// Exit code, or `None` when a signal ended it
+    Exited(Option<i32>),
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum SyslogProtocol {
+    Udp,
+    Tcp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum LogEncoding {
+    /// UTF-8 when the line is valid, otherwise Windows-1252
+    Auto,
+    Utf8,
+    /// Windows-1252, the common superset of Latin-1
+    Latin1,
+}
+
+/// Row actions chosen from the log grid's context menu
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RowAction {
+    TogglePin,
+    OpenDetails,
+    DeleteSelected,
+    RunCommand,
+}
+
+/// Messages sent from the collection thread to the UI
+enum CollectorMessage {
+    Line(String),
+    /// A line the command wrote to stderr
+    StderrLine(String),
+    Error(String),
+    /// The command couldn't be spawned
+    SpawnFailed(String),
+    /// Bytes read so far while importing a file
+    Progress(u64),
+    /// The command was spawned successfully
+    Started,
+    /// The command exited on its own with this code; `None` when a signal ended it
+    Exited(Option<i32>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 enum TimeUnit {
     Minutes,
     Hours,
@@ -57,6 +472,18 @@ enum TimeUnit {
 }
 
 impl PredefinedSpan {
+    /// Every span, narrowest first
+    const ALL: [PredefinedSpan; 8] = [
+        PredefinedSpan::Last15Minutes,
+        PredefinedSpan::Last30Minutes,
+        PredefinedSpan::Last1Hour,
+        PredefinedSpan::Last6Hours,
+        PredefinedSpan::Last24Hours,
+        PredefinedSpan::Last3Days,
+        PredefinedSpan::Last1Week,
+        PredefinedSpan::Last1Month,
+    ];
+
     fn display_name(&self) -> &'static str {
         match self {
             PredefinedSpan::Last15Minutes => "Last 15 minutes",
@@ -84,6 +511,148 @@ impl PredefinedSpan {
     }
 }
 
+impl SearchScope {
+    fn display_name(&self) -> &'static str {
+        match self {
+            SearchScope::Content => "Content",
+            SearchScope::Timestamp => "Timestamp",
+            SearchScope::Both => "Both",
+            SearchScope::RawLine => "Raw line",
+        }
+    }
+}
+
+impl TimeSeparator {
+    fn display_name(&self) -> &'static str {
+        match self {
+            TimeSeparator::Off => "Off",
+            TimeSeparator::Hourly => "Hourly",
+            TimeSeparator::Daily => "Daily",
+        }
+    }
+
+    /// Truncate a timestamp to its bucket; a change of bucket marks a boundary
+    fn bucket(&self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            TimeSeparator::Off => None,
+            TimeSeparator::Hourly => time.date().and_hms_opt(time.hour(), 0, 0),
+            TimeSeparator::Daily => time.date().and_hms_opt(0, 0, 0),
+        }
+    }
+
+    fn label(&self, bucket: NaiveDateTime) -> String {
+        match self {
+            TimeSeparator::Daily => bucket.format("%Y-%m-%d").to_string(),
+            _ => bucket.format("%Y-%m-%d %H:00").to_string(),
+        }
+    }
+}
+
+impl Theme {
+    fn display_name(&self) -> &'static str {
+        match self {
+            Theme::Dark => "Dark",
+            Theme::Light => "Light",
+            Theme::HighContrast => "High contrast",
+        }
+    }
+
+    fn visuals(&self) -> egui::Visuals {
+        match self {
+            Theme::Dark => egui::Visuals::dark(),
+            Theme::Light => egui::Visuals::light(),
+            Theme::HighContrast => {
+                let mut visuals = egui::Visuals::dark();
+                let border = egui::Stroke::new(2.0, egui::Color32::WHITE);
+                visuals.override_text_color = Some(egui::Color32::WHITE);
+                visuals.panel_fill = egui::Color32::BLACK;
+                visuals.window_fill = egui::Color32::BLACK;
+                visuals.extreme_bg_color = egui::Color32::BLACK;
+                visuals.faint_bg_color = egui::Color32::from_gray(45);
+                visuals.window_stroke = border;
+                visuals.selection.bg_fill = egui::Color32::from_rgb(0, 80, 200);
+                visuals.selection.stroke = border;
+                visuals.hyperlink_color = egui::Color32::from_rgb(120, 200, 255);
+                for widget in [
+                    &mut visuals.widgets.noninteractive,
+                    &mut visuals.widgets.inactive,
+                    &mut visuals.widgets.hovered,
+                    &mut visuals.widgets.active,
+                    &mut visuals.widgets.open,
+                ] {
+                    widget.bg_stroke = border;
+                    widget.fg_stroke = egui::Stroke::new(1.5, egui::Color32::WHITE);
+                }
+                visuals.widgets.noninteractive.bg_stroke =
+                    egui::Stroke::new(1.0, egui::Color32::WHITE);
+                visuals.widgets.inactive.weak_bg_fill = egui::Color32::BLACK;
+                visuals.widgets.inactive.bg_fill = egui::Color32::BLACK;
+                visuals.widgets.hovered.weak_bg_fill = egui::Color32::from_gray(60);
+                visuals.widgets.active.weak_bg_fill = egui::Color32::from_gray(90);
+                visuals
+            }
+        }
+    }
+}
+
+impl LogEncoding {
+    fn display_name(&self) -> &'static str {
+        match self {
+            LogEncoding::Auto => "Auto",
+            LogEncoding::Utf8 => "UTF-8",
+            LogEncoding::Latin1 => "Latin-1 / Windows-1252",
+        }
+    }
+
+    /// Decode one raw line, dropping its line terminator
+    fn decode_line(&self, bytes: &[u8]) -> String {
+        let bytes = bytes
+            .strip_suffix(b"\n")
+            .map(|bytes| bytes.strip_suffix(b"\r").unwrap_or(bytes))
+            .unwrap_or(bytes);
+        match self {
+            LogEncoding::Auto => match std::str::from_utf8(bytes) {
+                Ok(text) => text.to_string(),
+                Err(_) => encoding_rs::WINDOWS_1252
+                    .decode_without_bom_handling(bytes)
+                    .0
+                    .into_owned(),
+            },
+            LogEncoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+            LogEncoding::Latin1 => encoding_rs::WINDOWS_1252
+                .decode_without_bom_handling(bytes)
+                .0
+                .into_owned(),
+        }
+    }
+}
+
+impl CollectionState {
+    fn badge(&self) -> (&'static str, egui::Color32) {
+        match self {
+            CollectionState::Tailing => ("tailing", egui::Color32::from_rgb(80, 200, 120)),
+            CollectionState::Paused => ("paused", egui::Color32::from_rgb(230, 190, 60)),
+            CollectionState::Stopped => ("stopped", egui::Color32::GRAY),
+            CollectionState::Error(_) => ("error", egui::Color32::from_rgb(230, 80, 80)),
+        }
+    }
+}
+
+impl ProcessStatus {
+    fn badge(&self) -> (String, egui::Color32) {
+        let red = egui::Color32::from_rgb(230, 80, 80);
+        match self {
+            ProcessStatus::Running => {
+                ("running".to_string(), egui::Color32::from_rgb(80, 200, 120))
+            }
+            ProcessStatus::Exited(Some(0)) => ("exited (0)".to_string(), egui::Color32::GRAY),
+            ProcessStatus::Exited(Some(code)) => (format!("exited ({code})"), red),
+            ProcessStatus::Exited(None) => ("killed by signal".to_string(), red),
+            ProcessStatus::Failed => ("failed to start".to_string(), red),
+        }
+    }
+}
+
 impl TimeUnit {
     fn display_name(&self) -> &'static str {
         match self {
@@ -105,109 +674,583 @@ impl TimeUnit {
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            version: SETTINGS_VERSION,
             log_command: "journalctl -f".to_string(),
             refresh_interval: 1000,
             favorite_commands: Vec::new(),
+            clear_on_restart: true,
+            dedup_field: String::new(),
+            dedup_window_secs: 60,
+            max_log_entries: 10000,
+            time_separator: TimeSeparator::Off,
+            stale_after_secs: 0,
+            window_size: None,
+            striped_rows: true,
+            stripe_color: None,
+            extraction_rules: Vec::new(),
+            skip_blank_lines: false,
+            time_filter: TimeFilter::default(),
+            placeholder_values: HashMap::new(),
+            max_entry_age_secs: None,
+            show_level_column: true,
+            theme: Theme::Dark,
+            colorize_levels: None,
+            relative_timestamps: false,
+            sample_every: 1,
+            sample_keep_levels: ["error", "err", "fatal", "critical", "crit"]
+                .iter()
+                .map(|level| level.to_string())
+                .collect(),
+            timestamp_display_format: DEFAULT_TIMESTAMP_FORMAT.to_string(),
+            row_action_command: String::new(),
+            show_row_action_output: true,
+            encoding: LogEncoding::Auto,
+            level_pattern: String::new(),
+            raw_mode: false,
+            favorite_cache_lines: 0,
+            burst_threshold: 0,
+            burst_window_secs: 60,
+            burst_notify: true,
+            syslog_protocol: SyslogProtocol::Udp,
+            syslog_port: 5514,
+            split_pattern: String::new(),
+            max_line_bytes: 64 * 1024,
+            favorites_search_first_above: 20,
+            backlog_lines: 0,
+            keybindings: KEY_ACTIONS
+                .iter()
+                .map(|(action, _, combo)| (action.to_string(), combo.to_string()))
+                .collect(),
+            first_token_timestamp: false,
+            first_token_format: "%Y-%m-%dT%H:%M:%S%.f".to_string(),
+            level_cues: HashMap::new(),
+            cues_muted: false,
+            sort_by_timestamp: false,
+            auto_clear_idle_mins: 0,
+            loading_timeout_secs: 3,
+            color_rules: Vec::new(),
+            use_shell: false,
+            env: Vec::new(),
+            working_dir: None,
+            auto_restart: false,
+            restart_backoff_secs: 1,
+            per_tab_filters: false,
+            follow_file: false,
+            ssh: None,
+        }
+    }
+}
+
+impl Default for TimeFilter {
+    fn default() -> Self {
+        let now = Local::now().naive_local();
+
+        Self {
+            mode: TimeSpanMode::Disabled,
+            custom_from_year: now.year(),
+            custom_from_month: now.month(),
+            custom_from_day: now.day(),
+            custom_from_hour: 0,
+            custom_from_minute: 0,
+            custom_to_year: now.year(),
+            custom_to_month: now.month(),
+            custom_to_day: now.day(),
+            custom_to_hour: 23,
+            custom_to_minute: 59,
+            relative_amount: 1,
+            relative_unit: TimeUnit::Hours,
+            invert: false,
         }
     }
 }
 
+#[derive(Clone)]
 struct LogEntry {
+    /// Stable identifier that survives buffer trimming and filtering
+    id: u64,
     timestamp: String,
     content: String,
+    /// The line as received, before timestamp extraction
+    raw: String,
+    /// Fields parsed from JSON or logfmt content
+    fields: Vec<(String, String)>,
+    received_at: Instant,
+    /// Level keyword detected in the content, if any
+    level: Option<&'static str>,
+    /// Read from the command's stderr rather than stdout
+    is_stderr: bool,
+}
+
+impl LogEntry {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Level keywords recognised in log lines by the level filter
+const LEVEL_KEYWORDS: [&str; 10] = [
+    "trace", "debug", "info", "warn", "warning", "error", "err", "fatal", "critical", "crit",
+];
+
+/// Attention cue for a level; both off by default
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+struct LevelCue {
+    sound: bool,
+    flash: bool,
+}
+
+/// Levels that can carry a cue, least severe first: settings key and label
+const CUE_LEVELS: [(&str, &str); 3] = [("warn", "WARN"), ("error", "ERROR"), ("fatal", "FATAL")];
+/// Minimum gap between two cues, so a flood of errors doesn't beep continuously
+const CUE_DEBOUNCE_SECS: u64 = 5;
+const CUE_FLASH_SECS: f32 = 0.8;
+
+/// Text color for lines matching `matcher`: a field query like `service=auth`
+/// or a regex, optionally written as `/payment/`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ColorRule {
+    matcher: String,
+    color: [u8; 3],
+}
+
+enum ColorMatcher {
+    Field(String),
+    Pattern(Regex),
+}
+
+impl ColorMatcher {
+    fn parse(matcher: &str) -> Option<Self> {
+        let matcher = matcher.trim();
+        if matcher.is_empty() {
+            return None;
+        }
+        if FIELD_QUERY_PATTERN.is_match(matcher) {
+            return Some(ColorMatcher::Field(matcher.to_string()));
+        }
+        let pattern = matcher
+            .strip_prefix('/')
+            .and_then(|inner| inner.strip_suffix('/'))
+            .unwrap_or(matcher);
+        Regex::new(pattern).ok().map(ColorMatcher::Pattern)
+    }
+}
+
+/// State of the group-by window. Counts are cached since grouping a large
+/// buffer every frame isn't free.
+#[derive(Default)]
+struct GroupBySummary {
+    /// Field to group on; `None` groups by detected level
+    field: Option<String>,
+    /// Field, next entry id, buffer length and filters the counts were computed for
+    computed_for: Option<(Option<String>, u64, usize, Vec<String>)>,
+    computed_at: Option<Instant>,
+    /// Largest group first; `None` collects entries lacking the field
+    counts: Vec<(Option<String>, usize)>,
+    /// Fields seen in the filtered entries, offered as grouping choices
+    field_names: Vec<String>,
+}
+
+/// Minimum gap between recomputing group counts while lines keep arriving
+const GROUP_BY_REFRESH_SECS: u64 = 1;
+
+/// Fields of the journalctl command builder
+#[derive(Debug, Clone)]
+struct JournalctlForm {
+    unit: String,
+    /// Index into `JOURNALCTL_PRIORITIES`; `None` shows every priority
+    priority: Option<usize>,
+    use_time_filter: bool,
+    grep: String,
+    follow: bool,
+}
+
+impl Default for JournalctlForm {
+    fn default() -> Self {
+        Self {
+            unit: String::new(),
+            priority: None,
+            use_time_filter: false,
+            grep: String::new(),
+            follow: true,
+        }
+    }
+}
+
+/// Values for `journalctl -p`, most severe first
+const JOURNALCTL_PRIORITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+/// What a file dialog was opened for; decides what happens with the picked path
+enum FileDialogPurpose {
+    OpenLogFile,
+    ExportRecipe,
+    Screenshot { logs_only: bool },
+    WorkingDir,
+}
+
+/// A screenshot waiting to be requested or to arrive
+struct PendingScreenshot {
+    path: PathBuf,
+    /// Crop to the log panel instead of saving the whole window
+    logs_only: bool,
+    requested: bool,
+}
+
+/// A transient notification shown in the bottom-right corner
+struct Toast {
+    message: String,
+    is_error: bool,
+    created: Instant,
+}
+
+impl Toast {
+    fn lifetime(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(if self.is_error { 8 } else { 4 })
+    }
 }
 
+/// Lines moved from the collector channel into the buffer per frame
+const MAX_LINES_PER_FRAME: usize = 2_000;
+const SEARCH_INPUT_ID: &str = "search_input";
+const COMMAND_INPUT_ID: &str = "command_input";
+/// Shown in place of the command while reading piped input
+const STDIN_LABEL: &str = "<stdin>";
+
+/// Most text "Copy as text" puts on the clipboard; older lines are left out
+const COPY_TEXT_MAX_BYTES: usize = 16 * 1024 * 1024;
+
+/// Rebindable shortcuts: action name, label and default combo
+const KEY_ACTIONS: [(&str, &str, &str); 7] = [
+    ("next_row", "Next row", "J"),
+    ("previous_row", "Previous row", "K"),
+    ("open_details", "Open details", "Enter"),
+    ("close_details", "Close details", "Escape"),
+    ("widen_time_window", "Widen time window", "Plus"),
+    ("narrow_time_window", "Narrow time window", "Minus"),
+    ("toggle_minimal_mode", "Minimal mode", "F11"),
+];
+
+/// Bumped whenever a settings change needs `migrate_settings` to rewrite old files
+const SETTINGS_VERSION: u32 = 1;
+
+/// Format entries are normalized to, and the default display format
+const DEFAULT_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Whole seconds only, since that is all the normalized timestamps keep
+const TIMESTAMP_FORMAT_PRESETS: [(&str, &str); 3] = [
+    ("Default", DEFAULT_TIMESTAMP_FORMAT),
+    ("ISO 8601", "%Y-%m-%dT%H:%M:%S"),
+    ("Time only", "%H:%M:%S"),
+];
+
+/// Upper bound for the automatic restart delay. A command that ran at least
+/// this long before exiting starts over from the configured delay.
+const MAX_RESTART_BACKOFF_SECS: u64 = 60;
+
+/// How often a followed file is checked for appended bytes
+const FOLLOW_POLL_MS: u64 = 250;
+
+/// Seconds without any output before the loading view hints at a silent command
+const LOADING_HINT_AFTER_SECS: u64 = 10;
+
+/// Built-in starting points offered in the favorites window; never persisted
+const COMMAND_TEMPLATES: [(&str, &str); 8] = [
+    ("journalctl follow unit", "journalctl -f -u {unit}"),
+    ("journalctl follow all", "journalctl -f"),
+    ("journalctl since boot, errors", "journalctl -b -p err -f"),
+    (
+        "docker logs follow",
+        "docker logs -f --tail 200 {container}",
+    ),
+    (
+        "docker compose logs follow",
+        "docker compose logs -f {service}",
+    ),
+    (
+        "kubectl logs follow pod",
+        "kubectl logs -f -n {namespace} {pod}",
+    ),
+    (
+        "kubectl logs follow deployment",
+        "kubectl logs -f -n {namespace} deployment/{deployment}",
+    ),
+    ("tail file", "tail -F {path}"),
+];
+
+/// Backgrounds for highlighted search terms, assigned in the order terms are
+/// typed and cycled when there are more terms than colors
+const TERM_HIGHLIGHT_COLORS: [egui::Color32; 5] = [
+    egui::Color32::from_rgb(250, 220, 80),
+    egui::Color32::from_rgb(90, 220, 230),
+    egui::Color32::from_rgb(240, 140, 220),
+    egui::Color32::from_rgb(150, 230, 120),
+    egui::Color32::from_rgb(250, 170, 90),
+];
+
+/// Conservative URL match: an explicit http(s) scheme up to whitespace or a
+/// character that usually delimits a URL in prose
+static URL_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"https?://[^\s<>"'`]+"#).expect("valid URL pattern"));
+
+static PLACEHOLDER_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{([A-Za-z_][A-Za-z0-9_-]*)\}").expect("valid placeholder pattern")
+});
+
+static FIELD_QUERY_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*([A-Za-z_][\w.\-]*)\s*(>=|<=|!=|>|<|=)\s*(.*?)\s*$")
+        .expect("valid field query pattern")
+});
+
+static DOCKER_AVAILABLE: LazyLock<bool> = LazyLock::new(|| LogsApp::on_path("docker"));
+
+static KUBECTL_AVAILABLE: LazyLock<bool> = LazyLock::new(|| LogsApp::on_path("kubectl"));
+
+static LOGFMT_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"([A-Za-z_][\w.\-]*)=("(?:[^"\\]|\\.)*"|\S*)"#).expect("valid logfmt pattern")
+});
+
 struct LogsApp {
     settings: Settings,
-    logs: Vec<LogEntry>,
     selected_log_levels: HashSet<String>,
     filter_mode: FilterMode,
     search_text: String,
+    search_scope: SearchScope,
     auto_scroll: bool,
     show_settings: bool,
-    log_receiver: Option<mpsc::Receiver<String>>,
-    log_thread_handle: Option<thread::JoinHandle<()>>,
-    settings_changed: bool,
+    /// Pattern being entered in the "open files matching" window, when open
+    glob_pattern_input: Option<String>,
+    /// A settings reload changed the command; ask before restarting
+    reload_restart_prompt: bool,
+    /// The journalctl builder window, when open
+    journalctl_form: Option<JournalctlForm>,
+    /// The group-by window, when open
+    group_by: Option<GroupBySummary>,
+    /// The Docker container picker, when open
+    docker_picker: Option<DockerPicker>,
+    /// The Kubernetes pod picker, when open
+    pod_picker: Option<PodPicker>,
+    pending_screenshot: Option<PendingScreenshot>,
+    /// File dialog shown by a worker thread so frames keep rendering meanwhile
+    file_dialog: Option<(FileDialogPurpose, mpsc::Receiver<Option<PathBuf>>)>,
+    last_cue: Option<Instant>,
+    /// Start and color of the flash currently fading out
+    cue_flash: Option<(Instant, egui::Color32)>,
+    /// Where the log panel was drawn last frame, for cropping screenshots
+    logs_panel_rect: Option<egui::Rect>,
+    /// Buffer contents saved with "Take snapshot"
+    snapshot: Option<Vec<String>>,
+    /// Diff shown in the snapshot comparison window, when open
+    snapshot_diff: Option<Vec<(similar::ChangeTag, String)>>,
+    diff_only_changes: bool,
+    syslog_window_open: bool,
+    /// Hide entries stamped before `new_since_mark`
+    only_new_since_mark: bool,
+    /// App start time until moved with the "mark now" button
+    new_since_mark: NaiveDateTime,
+    /// Row action threads report their stdout, or an error, back here
+    row_action_tx: mpsc::Sender<Result<String, String>>,
+    row_action_rx: mpsc::Receiver<Result<String, String>>,
     current_level_filter: String,
     show_favorites: bool,
     new_favorite_name: String,
     favorite_search_text: String,
+    /// Escape hatch from the search-first favorites view
+    show_all_favorites: bool,
+    new_favorite_with_filters: bool,
     editing_favorite_index: Option<usize>,
     edit_favorite_name: String,
     edit_favorite_command: String,
-    time_span_mode: TimeSpanMode,
-    custom_from_year: i32,
-    custom_from_month: u32,
-    custom_from_day: u32,
-    custom_from_hour: u32,
-    custom_from_minute: u32,
-    custom_to_year: i32,
-    custom_to_month: u32,
-    custom_to_day: u32,
-    custom_to_hour: u32,
-    custom_to_minute: u32,
-    relative_amount: i32,
-    relative_unit: TimeUnit,
-    is_loading: bool,
+    /// Compiled form of `Settings::extraction_rules`, skipping invalid patterns
+    extraction_patterns: Vec<Regex>,
+    /// Compiled `Settings::level_pattern`; `None` when empty or invalid
+    level_regex: Option<Regex>,
+    /// Compiled `Settings::split_pattern`; `None` when empty, invalid or without a group
+    split_regex: Option<Regex>,
+    /// Valid entries of `Settings::color_rules`, in order
+    compiled_color_rules: Vec<(ColorMatcher, egui::Color32)>,
+    toasts: Vec<Toast>,
+    /// Handed to collection threads so new lines wake the UI immediately
+    egui_ctx: Option<egui::Context>,
+    next_entry_id: u64,
+    /// Row under the keyboard cursor
+    selected_entry: Option<u64>,
+    /// Extra rows added to the selection with Ctrl- or Shift-click
+    marked_entries: HashSet<u64>,
+    scroll_to_selected: bool,
+    /// Copies of pinned rows, kept even after the originals are trimmed
+    pinned_entries: Vec<LogEntry>,
+    /// Entry shown in the detail window
+    detail_entry: Option<u64>,
+    /// Command template waiting for its placeholders to be filled in
+    placeholder_command: Option<String>,
+    /// Favorite the waiting command came from
+    placeholder_favorite: Option<u64>,
+    /// Fullscreen view showing only the log grid
+    minimal_mode: bool,
+    /// One source per tab, never empty
+    tabs: Vec<LogSource>,
+    active_tab: usize,
 }
 
 impl Default for LogsApp {
     fn default() -> Self {
-        let mut selected_log_levels = HashSet::new();
-        selected_log_levels.insert("trace".to_string());
-        selected_log_levels.insert("debug".to_string());
-        selected_log_levels.insert("info".to_string());
-        selected_log_levels.insert("warn".to_string());
-        selected_log_levels.insert("warning".to_string());
-        selected_log_levels.insert("error".to_string());
-        selected_log_levels.insert("err".to_string());
-        selected_log_levels.insert("fatal".to_string());
-        selected_log_levels.insert("critical".to_string());
-        selected_log_levels.insert("crit".to_string());
+        Self::with_settings(Self::load_settings())
+    }
+}
 
-        let now = Local::now().naive_local();
+/// Text color for lines read from stderr, unless a color rule matches
+const STDERR_TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(230, 140, 60);
+
+/// How long a stopped command gets to exit after SIGTERM before it is killed
+const TERMINATE_GRACE_MS: u64 = 500;
+
+/// Columns in the split view, counting the "other" column
+const MAX_SPLIT_STREAMS: usize = 6;
+
+/// Longest newline-framed TCP syslog message kept whole, like a UDP datagram
+const SYSLOG_MAX_FRAME_BYTES: usize = 64 * 1024;
+
+/// Passes reads through while tallying the bytes seen
+struct CountingReader<R> {
+    inner: R,
+    count: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count.fetch_add(read as u64, Ordering::Relaxed);
+        Ok(read)
+    }
+}
+
+impl LogsApp {
+    fn new(cc: &eframe::CreationContext<'_>, read_stdin: bool) -> Self {
+        let mut app = Self {
+            egui_ctx: Some(cc.egui_ctx.clone()),
+            ..Self::default()
+        };
+        cc.egui_ctx.set_visuals(app.settings.theme.visuals());
+
+        // Always start log collection immediately; piped input replaces the command
+        if read_stdin {
+            app.start_stdin_collection();
+        } else {
+            app.start_log_collection(0);
+        }
+        app
+    }
 
+    /// An app using `settings` instead of the ones in the settings file
+    fn with_settings(settings: Settings) -> Self {
+        let selected_log_levels = LEVEL_KEYWORDS
+            .iter()
+            .map(|level| level.to_string())
+            .collect();
+
+        let (row_action_tx, row_action_rx) = mpsc::channel();
+
+        let source = LogSource::new(
+            settings.log_command.clone(),
+            settings.follow_file,
+            settings.ssh.clone(),
+        );
         let mut app = Self {
-            settings: Self::load_settings(),
-            logs: Vec::new(),
+            settings,
             selected_log_levels,
             filter_mode: FilterMode::IncludeSelected,
             search_text: String::new(),
+            search_scope: SearchScope::Both,
             auto_scroll: true,
             show_settings: false,
-            log_receiver: None,
-            log_thread_handle: None,
-            settings_changed: false,
+            glob_pattern_input: None,
+            reload_restart_prompt: false,
+            journalctl_form: None,
+            group_by: None,
+            docker_picker: None,
+            pod_picker: None,
+            pending_screenshot: None,
+            file_dialog: None,
+            last_cue: None,
+            cue_flash: None,
+            logs_panel_rect: None,
+            snapshot: None,
+            snapshot_diff: None,
+            diff_only_changes: true,
+            syslog_window_open: false,
+            only_new_since_mark: false,
+            new_since_mark: Local::now().naive_local(),
+            row_action_tx,
+            row_action_rx,
             current_level_filter: "All Levels".to_string(),
             show_favorites: false,
             new_favorite_name: String::new(),
             favorite_search_text: String::new(),
+            show_all_favorites: false,
+            new_favorite_with_filters: false,
             editing_favorite_index: None,
             edit_favorite_name: String::new(),
             edit_favorite_command: String::new(),
-            time_span_mode: TimeSpanMode::Disabled,
-            custom_from_year: now.year(),
-            custom_from_month: now.month(),
-            custom_from_day: now.day(),
-            custom_from_hour: 0,
-            custom_from_minute: 0,
-            custom_to_year: now.year(),
-            custom_to_month: now.month(),
-            custom_to_day: now.day(),
-            custom_to_hour: 23,
-            custom_to_minute: 59,
-            relative_amount: 1,
-            relative_unit: TimeUnit::Hours,
-            is_loading: false,
+            pinned_entries: Vec::new(),
+            marked_entries: HashSet::new(),
+            extraction_patterns: Vec::new(),
+            toasts: Vec::new(),
+            egui_ctx: None,
+            next_entry_id: 0,
+            selected_entry: None,
+            scroll_to_selected: false,
+            detail_entry: None,
+            placeholder_command: None,
+            placeholder_favorite: None,
+            minimal_mode: false,
+            tabs: vec![source],
+            active_tab: 0,
+            level_regex: None,
+            split_regex: None,
+            compiled_color_rules: Vec::new(),
         };
 
-        // Always start log collection immediately
-        app.start_log_collection();
+        app.compile_extraction_rules();
+        app.compile_level_pattern();
+        app.compile_split_pattern();
+        app.compile_color_rules();
         app
     }
-}
 
-impl LogsApp {
+    /// The source shown in the active tab
+    fn source(&self) -> &LogSource {
+        &self.tabs[self.active_tab]
+    }
+
+    fn source_mut(&mut self) -> &mut LogSource {
+        &mut self.tabs[self.active_tab]
+    }
+
+    /// Whether stdin is a pipe or a redirected file. Launchers and services
+    /// hand over `/dev/null`, a socket or a terminal instead, which aren't input
+    #[cfg(unix)]
+    fn stdin_is_piped() -> bool {
+        use std::os::fd::AsFd;
+        use std::os::unix::fs::FileTypeExt;
+        let Ok(stdin) = std::io::stdin().as_fd().try_clone_to_owned() else {
+            return false;
+        };
+        fs::File::from(stdin)
+            .metadata()
+            .is_ok_and(|metadata| metadata.file_type().is_fifo() || metadata.file_type().is_file())
+    }
+
+    /// Without a portable file type check, piped input needs an explicit `-`
+    #[cfg(not(unix))]
+    fn stdin_is_piped() -> bool {
+        false
+    }
+
     fn get_config_path() -> PathBuf {
         let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
         path.push("logs-viewer");
@@ -215,35 +1258,415 @@ impl LogsApp {
         path
     }
 
+    /// Show the settings directory in the OS file manager, creating it if needed
+    fn open_config_folder(&mut self) {
+        let config_path = Self::get_config_path();
+        let Some(folder) = config_path.parent() else {
+            return;
+        };
+        let result = fs::create_dir_all(folder)
+            .map_err(|err| err.to_string())
+            .and_then(|()| opener::open(folder).map_err(|err| err.to_string()));
+        if let Err(err) = result {
+            self.notify_error(format!("Failed to open {}: {err}", folder.display()));
+        }
+    }
+
     fn load_settings() -> Settings {
         let config_path = Self::get_config_path();
-        if let Ok(content) = fs::read_to_string(&config_path) {
-            serde_json::from_str(&content).unwrap_or_default()
-        } else {
-            Settings::default()
+        let Ok(content) = fs::read_to_string(&config_path) else {
+            return Settings::default();
+        };
+        let Ok(mut value) = serde_json::from_str::<serde_json::Value>(&content) else {
+            Self::backup_settings_file(&config_path);
+            return Settings::default();
+        };
+
+        Self::migrate_settings(&mut value);
+        let mut settings = match serde_json::from_value::<Settings>(value.clone()) {
+            Ok(settings) => settings,
+            Err(_) => {
+                // Keep a copy of what couldn't be read before it gets overwritten
+                Self::backup_settings_file(&config_path);
+                Self::salvage_settings(value)
+            }
+        };
+        settings.version = SETTINGS_VERSION;
+        settings
+    }
+
+    /// Bring an older settings file up to `SETTINGS_VERSION`, one step per version
+    fn migrate_settings(value: &mut serde_json::Value) {
+        let Some(object) = value.as_object_mut() else {
+            return;
+        };
+        let version = object
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0);
+
+        if version < 1 {
+            // Favorites saved before ids existed all load as 0
+            if let Some(favorites) = object
+                .get_mut("favorite_commands")
+                .and_then(serde_json::Value::as_array_mut)
+            {
+                let mut next_id = favorites
+                    .iter()
+                    .filter_map(|favorite| favorite.get("id")?.as_u64())
+                    .max()
+                    .unwrap_or(0);
+                for favorite in favorites.iter_mut().filter_map(|f| f.as_object_mut()) {
+                    if favorite
+                        .get("id")
+                        .and_then(serde_json::Value::as_u64)
+                        .unwrap_or(0)
+                        == 0
+                    {
+                        next_id += 1;
+                        favorite.insert("id".to_string(), next_id.into());
+                    }
+                }
+            }
+        }
+
+        object.insert("version".to_string(), SETTINGS_VERSION.into());
+    }
+
+    /// Field-by-field fallback for a file that doesn't deserialize as a whole:
+    /// every field that still reads is kept, unreadable ones fall back to their
+    /// defaults, and unreadable favorites are dropped one by one
+    fn salvage_settings(value: serde_json::Value) -> Settings {
+        let serde_json::Value::Object(fields) = value else {
+            return Settings::default();
+        };
+        let Ok(serde_json::Value::Object(mut merged)) = serde_json::to_value(Settings::default())
+        else {
+            return Settings::default();
+        };
+
+        for (key, mut field) in fields {
+            if key == "favorite_commands"
+                && let Some(favorites) = field.as_array_mut()
+            {
+                favorites.retain(|favorite| {
+                    serde_json::from_value::<FavoriteCommand>(favorite.clone()).is_ok()
+                });
+            }
+            let mut candidate = merged.clone();
+            candidate.insert(key, field);
+            let candidate = serde_json::Value::Object(candidate);
+            if serde_json::from_value::<Settings>(candidate.clone()).is_ok()
+                && let serde_json::Value::Object(candidate) = candidate
+            {
+                merged = candidate;
+            }
+        }
+
+        serde_json::from_value(serde_json::Value::Object(merged)).unwrap_or_default()
+    }
+
+    fn backup_settings_file(config_path: &Path) {
+        let _ = fs::copy(config_path, config_path.with_extension("json.bak"));
+    }
+
+    fn favorite_cache_path(id: u64) -> PathBuf {
+        let mut path = Self::get_config_path();
+        path.set_file_name("cache");
+        path.push(format!("favorite-{id}.json"));
+        path
+    }
+
+    /// Write the tail of the buffer for the favorite running in tab `tab`
+    fn save_favorite_cache(&mut self, tab: usize) {
+        let source = &self.tabs[tab];
+        let Some(id) = source.active_favorite else {
+            return;
+        };
+        let limit = self.settings.favorite_cache_lines;
+        if limit == 0 || source.logs.is_empty() {
+            return;
+        }
+        let lines: Vec<&str> = source.logs[source.logs.len().saturating_sub(limit)..]
+            .iter()
+            .map(|entry| entry.raw.as_str())
+            .collect();
+        let path = Self::favorite_cache_path(id);
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| Ok(serde_json::to_string(&lines)?))
+            .and_then(|content| fs::write(&path, content));
+        if let Err(err) = result {
+            self.notify_error(format!("Failed to cache favorite output: {err}"));
+        }
+    }
+
+    fn load_favorite_cache(id: u64) -> Vec<String> {
+        fs::read_to_string(Self::favorite_cache_path(id))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_settings(&mut self) {
+        // The active tab's source is the one restored on the next launch
+        let source = &self.tabs[self.active_tab];
+        self.settings.log_command = source.command.clone();
+        self.settings.follow_file = source.follow_file;
+        self.settings.ssh = source.ssh.clone();
+        if let Err(err) = self.write_settings() {
+            self.notify_error(format!("Failed to save settings: {err}"));
         }
     }
 
-    fn save_settings(&self) {
+    fn write_settings(&self) -> std::io::Result<()> {
         let config_path = Self::get_config_path();
         if let Some(parent) = config_path.parent() {
-            let _ = fs::create_dir_all(parent);
+            fs::create_dir_all(parent)?;
         }
-        if let Ok(content) = serde_json::to_string_pretty(&self.settings) {
-            let _ = fs::write(&config_path, content);
+        let content = serde_json::to_string_pretty(&self.settings)?;
+        fs::write(&config_path, content)
+    }
+
+    fn notify(&mut self, message: impl Into<String>) {
+        self.toasts.push(Toast {
+            message: message.into(),
+            is_error: false,
+            created: Instant::now(),
+        });
+    }
+
+    fn notify_error(&mut self, message: impl Into<String>) {
+        self.toasts.push(Toast {
+            message: message.into(),
+            is_error: true,
+            created: Instant::now(),
+        });
+    }
+
+    /// Ask for a PNG path; the screenshot is requested once the dialog is
+    /// gone so neither it nor the closing menu is captured
+    fn request_screenshot(&mut self, logs_only: bool) {
+        self.open_file_dialog(FileDialogPurpose::Screenshot { logs_only }, || {
+            rfd::FileDialog::new()
+                .add_filter("PNG image", &["png"])
+                .set_file_name("logs.png")
+                .save_file()
+        });
+    }
+
+    /// Run `show` on a worker thread, since native dialogs block until they
+    /// close. Ignored while another dialog is open.
+    fn open_file_dialog(
+        &mut self,
+        purpose: FileDialogPurpose,
+        show: impl FnOnce() -> Option<PathBuf> + Send + 'static,
+    ) {
+        if self.file_dialog.is_some() {
+            return;
         }
+        let (tx, rx) = mpsc::channel();
+        let repaint_ctx = self.egui_ctx.clone();
+        thread::spawn(move || {
+            let _ = tx.send(show());
+            if let Some(ctx) = repaint_ctx {
+                ctx.request_repaint();
+            }
+        });
+        self.file_dialog = Some((purpose, rx));
     }
 
-    fn add_favorite_command(&mut self, name: String, command: String) {
-        self.settings
+    /// Act on the path picked in the open file dialog once it closes
+    fn poll_file_dialog(&mut self) {
+        let Some((_, receiver)) = &self.file_dialog else {
+            return;
+        };
+        let picked = match receiver.try_recv() {
+            Ok(picked) => picked,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => None,
+        };
+        let Some((purpose, _)) = self.file_dialog.take() else {
+            return;
+        };
+        let Some(path) = picked else {
+            return;
+        };
+        match purpose {
+            FileDialogPurpose::OpenLogFile => self.open_log_file(path),
+            FileDialogPurpose::ExportRecipe => self.export_recipe(&path),
+            FileDialogPurpose::Screenshot { logs_only } => {
+                self.pending_screenshot = Some(PendingScreenshot {
+                    path,
+                    logs_only,
+                    requested: false,
+                });
+            }
+            FileDialogPurpose::WorkingDir => {
+                self.settings.working_dir = Some(path);
+                self.save_settings();
+            }
+        }
+    }
+
+    /// Screenshots arrive as an input event some frames after they are requested
+    fn handle_screenshot(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &mut self.pending_screenshot else {
+            return;
+        };
+        if !pending.requested {
+            pending.requested = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot);
+            ctx.request_repaint();
+            return;
+        }
+
+        let screenshot = ctx.input(|i| {
+            i.events.iter().find_map(|event| match event {
+                egui::Event::Screenshot { image, .. } => Some(Arc::clone(image)),
+                _ => None,
+            })
+        });
+        let Some(screenshot) = screenshot else {
+            ctx.request_repaint();
+            return;
+        };
+        let Some(pending) = self.pending_screenshot.take() else {
+            return;
+        };
+
+        let screenshot = match self.logs_panel_rect {
+            Some(rect) if pending.logs_only => {
+                screenshot.region(&rect, Some(ctx.pixels_per_point()))
+            }
+            _ => (*screenshot).clone(),
+        };
+        let result = image::save_buffer(
+            &pending.path,
+            screenshot.as_raw(),
+            screenshot.width() as u32,
+            screenshot.height() as u32,
+            image::ExtendedColorType::Rgba8,
+        );
+        match result {
+            Ok(()) => self.notify(format!("Saved {}", pending.path.display())),
+            Err(err) => self.notify_error(format!("Could not save screenshot: {err}")),
+        }
+    }
+
+    fn show_toasts(&mut self, ctx: &egui::Context) {
+        self.toasts
+            .retain(|toast| toast.created.elapsed() < toast.lifetime());
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-12.0, -12.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for (index, toast) in self.toasts.iter().enumerate() {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        let color = if toast.is_error {
+                            egui::Color32::from_rgb(230, 80, 80)
+                        } else {
+                            ui.visuals().text_color()
+                        };
+                        let text = egui::RichText::new(&toast.message).color(color);
+                        if ui
+                            .add(egui::Label::new(text).sense(egui::Sense::click()))
+                            .on_hover_text("Click to dismiss")
+                            .clicked()
+                        {
+                            dismissed = Some(index);
+                        }
+                    });
+                }
+            });
+
+        if let Some(index) = dismissed {
+            self.toasts.remove(index);
+        }
+        ctx.request_repaint_after(std::time::Duration::from_millis(250));
+    }
+
+    fn add_favorite_command(
+        &mut self,
+        name: String,
+        command: String,
+        filters: Option<FavoriteFilters>,
+    ) {
+        let id = self
+            .settings
             .favorite_commands
-            .push(FavoriteCommand { name, command });
+            .iter()
+            .map(|favorite| favorite.id)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        self.settings.favorite_commands.push(FavoriteCommand {
+            id,
+            name,
+            command,
+            filters,
+        });
+        self.save_settings();
+    }
+
+    /// Reset every view filter so all buffered lines show
+    fn clear_filters(&mut self) {
+        self.clear_content_filters();
+        self.settings.time_filter.mode = TimeSpanMode::Disabled;
+        self.settings.time_filter.invert = false;
+        self.only_new_since_mark = false;
         self.save_settings();
     }
 
+    /// Reset the level and search filters but keep the time window
+    fn clear_content_filters(&mut self) {
+        self.selected_log_levels.clear();
+        self.current_level_filter = "All Levels".to_string();
+        self.filter_mode = FilterMode::IncludeSelected;
+        self.search_text.clear();
+    }
+
+    fn current_filters(&self) -> FavoriteFilters {
+        FavoriteFilters {
+            selected_log_levels: self.selected_log_levels.clone(),
+            filter_mode: self.filter_mode.clone(),
+            search_text: self.search_text.clone(),
+            search_scope: self.search_scope.clone(),
+            time_filter: self.settings.time_filter.clone(),
+        }
+    }
+
+    fn apply_filters(&mut self, filters: FavoriteFilters) {
+        self.current_level_filter = match filters.selected_log_levels.iter().next() {
+            Some(level) if filters.selected_log_levels.len() == 1 => level.to_uppercase(),
+            _ => "All Levels".to_string(),
+        };
+        self.selected_log_levels = filters.selected_log_levels;
+        self.filter_mode = filters.filter_mode;
+        self.search_text = filters.search_text;
+        self.search_scope = filters.search_scope;
+        if self.settings.time_filter != filters.time_filter {
+            self.settings.time_filter = filters.time_filter;
+            self.save_settings();
+        }
+    }
+
     fn remove_favorite_command(&mut self, index: usize) {
         if index < self.settings.favorite_commands.len() {
-            self.settings.favorite_commands.remove(index);
+            let favorite = self.settings.favorite_commands.remove(index);
+            // Missing when caching is off or the favorite never ran
+            let _ = fs::remove_file(Self::favorite_cache_path(favorite.id));
+            for source in &mut self.tabs {
+                if source.active_favorite == Some(favorite.id) {
+                    source.active_favorite = None;
+                }
+            }
             self.save_settings();
         }
     }
@@ -256,82 +1679,919 @@ impl LogsApp {
         }
     }
 
-    fn apply_favorite_command(&mut self, command: String) {
-        self.settings.log_command = command;
+    fn apply_favorite_command(&mut self, index: usize) {
+        let Some(favorite) = self.settings.favorite_commands.get(index).cloned() else {
+            return;
+        };
+        if let Some(filters) = favorite.filters {
+            self.apply_filters(filters);
+        }
+        self.run_command(favorite.command, Some(favorite.id));
+    }
+
+    /// Run a command, first asking for values if it contains `{name}` placeholders.
+    /// `favorite` is the favorite it came from, which becomes active once it starts.
+    fn run_command(&mut self, command: String, favorite: Option<u64>) {
+        if !Self::command_placeholders(&command).is_empty() {
+            self.placeholder_command = Some(command);
+            self.placeholder_favorite = favorite;
+            return;
+        }
+        let source = self.source();
+        let unchanged = source.log_thread_handle.is_some()
+            && source.running_command.as_deref()
+                == Some(Self::effective_command(&command).as_str());
+        if unchanged {
+            self.source_mut().command = command;
+            self.notify("Command unchanged; use File > Restart Collection to force a restart");
+            return;
+        }
+        self.save_favorite_cache(self.active_tab);
+        let source = self.source_mut();
+        source.active_favorite = favorite;
+        source.command = command;
         self.restart_log_collection();
+
+        let cache_lines = self.settings.favorite_cache_lines;
+        let source = self.source_mut();
+        if let Some(id) = favorite
+            && cache_lines > 0
+            && source.is_loading
+        {
+            source.cached_preview = Self::load_favorite_cache(id);
+        }
     }
 
-    fn get_time_range(&self) -> Option<(NaiveDateTime, NaiveDateTime)> {
-        match &self.time_span_mode {
-            TimeSpanMode::Disabled => None,
-            TimeSpanMode::Predefined(span) => {
-                let now = Local::now().naive_local();
-                let duration = span.to_duration();
-                let from = now - duration;
-                Some((from, now))
+    /// Unique placeholder names in the order they appear
+    fn command_placeholders(command: &str) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for captures in PLACEHOLDER_PATTERN.captures_iter(command) {
+            if !names.iter().any(|name| name == &captures[1]) {
+                names.push(captures[1].to_string());
             }
-            TimeSpanMode::Custom => {
-                let from = NaiveDate::from_ymd_opt(
-                    self.custom_from_year,
-                    self.custom_from_month,
-                    self.custom_from_day,
-                )?
-                .and_time(NaiveTime::from_hms_opt(
-                    self.custom_from_hour,
-                    self.custom_from_minute,
-                    0,
-                )?);
+        }
+        names
+    }
 
-                let to = NaiveDate::from_ymd_opt(
-                    self.custom_to_year,
-                    self.custom_to_month,
-                    self.custom_to_day,
-                )?
-                .and_time(NaiveTime::from_hms_opt(
-                    self.custom_to_hour,
-                    self.custom_to_minute,
-                    59,
-                )?);
+    fn substitute_placeholders(command: &str, values: &HashMap<String, String>) -> String {
+        PLACEHOLDER_PATTERN
+            .replace_all(command, |captures: &regex::Captures| {
+                values
+                    .get(&captures[1])
+                    .cloned()
+                    .unwrap_or_else(|| captures[0].to_string())
+            })
+            .into_owned()
+    }
 
-                Some((from, to))
-            }
-            TimeSpanMode::Relative => {
-                let now = Local::now().naive_local();
-                let duration = self.relative_unit.to_duration(self.relative_amount as i64);
-                let from = now - duration;
-                Some((from, now))
-            }
+    fn show_placeholder_window(&mut self, ctx: &egui::Context) {
+        let Some(template) = self.placeholder_command.clone() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut run = false;
+        egui::Window::new("Fill in Command")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.monospace(&template);
+                ui.separator();
+                egui::Grid::new("placeholder_fields").show(ui, |ui| {
+                    for name in Self::command_placeholders(&template) {
+                        ui.label(&name);
+                        ui.text_edit_singleline(
+                            self.settings.placeholder_values.entry(name).or_default(),
+                        );
+                        ui.end_row();
+                    }
+                });
+                if ui.button("Run").clicked() {
+                    run = true;
+                }
+            });
+
+        if run {
+            let command =
+                Self::substitute_placeholders(&template, &self.settings.placeholder_values);
+            self.placeholder_command = None;
+            let favorite = self.placeholder_favorite.take();
+            self.run_command(command, favorite);
+            self.save_settings();
+        } else if !open {
+            self.placeholder_command = None;
+            self.placeholder_favorite = None;
         }
     }
 
-    fn parse_time_input(input: &str) -> Option<NaiveDateTime> {
-        if input.trim().is_empty() {
-            return None;
+    /// Re-read `settings.json` after it was edited by hand
+    fn reload_settings(&mut self, ctx: &egui::Context) {
+        let config_path = Self::get_config_path();
+        let content = match fs::read_to_string(&config_path) {
+            Ok(content) => content,
+            Err(err) => {
+                self.notify_error(format!("Could not read {}: {err}", config_path.display()));
+                return;
+            }
+        };
+        // Checked here because `load_settings` quietly falls back to defaults
+        if let Err(err) = serde_json::from_str::<Settings>(&content) {
+            self.notify_error(format!("Settings not reloaded: {err}"));
+            return;
         }
 
-        let trimmed = input.trim();
+        self.settings = Self::load_settings();
+        self.compile_extraction_rules();
+        self.compile_level_pattern();
+        self.compile_split_pattern();
+        self.compile_color_rules();
+        ctx.set_visuals(self.settings.theme.visuals());
+        let source = &mut self.tabs[self.active_tab];
+        if source
+            .active_favorite
+            .is_some_and(|id| !self.settings.favorite_commands.iter().any(|f| f.id == id))
+        {
+            source.active_favorite = None;
+        }
 
-        // Try full format first: "2025-09-15 12:23:30"
-        if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M:%S") {
-            return Some(dt);
+        source.command = self.settings.log_command.clone();
+        source.follow_file = self.settings.follow_file;
+        source.ssh = self.settings.ssh.clone();
+        let command = Self::effective_command(&source.command);
+        if source
+            .running_command
+            .as_ref()
+            .is_some_and(|running| *running != command)
+        {
+            self.reload_restart_prompt = true;
         }
+        self.notify("Settings reloaded");
+    }
 
-        // Try date + hour:minute: "2025-09-15 12:23"
-        if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M") {
-            return Some(dt);
+    /// Offered after a reload changed the command of the running collection
+    fn show_reload_restart_prompt(&mut self, ctx: &egui::Context) {
+        if !self.reload_restart_prompt {
+            return;
         }
 
-        // Try date + hour: "2025-09-15 12"
-        if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H") {
-            return Some(dt);
+        let mut restart = false;
+        let mut dismiss = false;
+        egui::Window::new("Command Changed")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("The reloaded settings run a different command. Restart collection?");
+                ui.horizontal(|ui| {
+                    restart = ui.button("Restart").clicked();
+                    dismiss = ui.button("Keep running").clicked();
+                });
+            });
+
+        if restart {
+            self.reload_restart_prompt = false;
+            self.restart_log_collection();
+        } else if dismiss {
+            self.reload_restart_prompt = false;
         }
+    }
 
-        // Try just date: "2025-09-15"
-        if let Ok(date) = NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
-            return Some(date.and_time(NaiveTime::from_hms_opt(0, 0, 0)?));
+    /// Assemble a `journalctl` command from the builder form, with every value
+    /// quoted so units, times and patterns can contain spaces
+    fn journalctl_command(
+        form: &JournalctlForm,
+        time_range: Option<(NaiveDateTime, NaiveDateTime)>,
+    ) -> Result<String, String> {
+        let mut parts = vec!["journalctl".to_string()];
+        if !form.unit.trim().is_empty() {
+            parts.extend(["-u".to_string(), form.unit.trim().to_string()]);
+        }
+        if let Some(priority) = form.priority {
+            parts.extend([
+                "-p".to_string(),
+                JOURNALCTL_PRIORITIES[priority].to_string(),
+            ]);
+        }
+        if form.use_time_filter
+            && let Some((since, until)) = time_range
+        {
+            // journalctl reads these as local time, like the time filter
+            let format = "%Y-%m-%d %H:%M:%S";
+            parts.push(format!("--since={}", since.format(format)));
+            // Following and an end time don't mix
+            if !form.follow {
+                parts.push(format!("--until={}", until.format(format)));
+            }
         }
+        if !form.grep.trim().is_empty() {
+            parts.push(format!("--grep={}", form.grep.trim()));
+        }
+        if form.follow {
+            parts.push("-f".to_string());
+        }
+        shlex::try_join(parts.iter().map(String::as_str))
+            .map_err(|_| "A value contains a character that can't be quoted".to_string())
+    }
 
-        None
+    fn show_journalctl_window(&mut self, ctx: &egui::Context) {
+        let time_range = self.get_time_range();
+        let Some(form) = self.journalctl_form.as_mut() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut use_command = false;
+        egui::Window::new("journalctl Builder")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("journalctl_form").show(ui, |ui| {
+                    ui.label("Unit:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut form.unit).hint_text("e.g. nginx.service"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Priority:");
+                    egui::ComboBox::from_id_source("journalctl_priority")
+                        .selected_text(
+                            form.priority
+                                .map_or("any", |index| JOURNALCTL_PRIORITIES[index]),
+                        )
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut form.priority, None, "any");
+                            for (index, name) in JOURNALCTL_PRIORITIES.iter().enumerate() {
+                                ui.selectable_value(&mut form.priority, Some(index), *name);
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Grep:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut form.grep).hint_text("regex, no spaces"),
+                    );
+                    ui.end_row();
+                });
+                ui.add_enabled(
+                    time_range.is_some(),
+                    egui::Checkbox::new(
+                        &mut form.use_time_filter,
+                        "Since/until from the time filter",
+                    ),
+                )
+                .on_disabled_hover_text("Set a time filter first");
+                ui.checkbox(&mut form.follow, "Follow new entries (-f)");
+
+                ui.separator();
+                match Self::journalctl_command(form, time_range) {
+                    Ok(command) => {
+                        ui.add(
+                            egui::Label::new(egui::RichText::new(&command).monospace())
+                                .selectable(true),
+                        );
+                        if ui.button("Use command").clicked() {
+                            use_command = true;
+                        }
+                    }
+                    Err(err) => {
+                        ui.colored_label(egui::Color32::from_rgb(230, 80, 80), err);
+                    }
+                }
+            });
+
+        if use_command && let Ok(command) = Self::journalctl_command(form, time_range) {
+            self.source_mut().command = command;
+            self.journalctl_form = None;
+        } else if !open {
+            self.journalctl_form = None;
+        }
+    }
+
+    /// Count entries per value of `field`, or per detected level when `None`,
+    /// largest group first
+    fn group_counts(entries: &[&LogEntry], field: Option<&str>) -> Vec<(Option<String>, usize)> {
+        let mut counts: HashMap<Option<String>, usize> = HashMap::new();
+        for entry in entries {
+            let value = match field {
+                Some(name) => entry.field(name).map(str::to_string),
+                None => entry.level.map(str::to_uppercase),
+            };
+            *counts.entry(value).or_default() += 1;
+        }
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+
+    /// Recompute the group-by counts when the field, buffer or filters
+    /// changed, at most once per `GROUP_BY_REFRESH_SECS` unless the field did
+    fn refresh_group_counts(&mut self, ctx: &egui::Context) {
+        let Some(summary) = &self.group_by else {
+            return;
+        };
+        let key = (
+            summary.field.clone(),
+            self.next_entry_id,
+            self.source().logs.len(),
+            self.filter_descriptions(),
+        );
+        if summary.computed_for.as_ref() == Some(&key) {
+            return;
+        }
+        let field_changed = summary
+            .computed_for
+            .as_ref()
+            .is_none_or(|(field, ..)| *field != key.0);
+        let refresh = std::time::Duration::from_secs(GROUP_BY_REFRESH_SECS);
+        if !field_changed
+            && let Some(elapsed) = summary.computed_at.map(|at| at.elapsed())
+            && elapsed < refresh
+        {
+            ctx.request_repaint_after(refresh - elapsed);
+            return;
+        }
+
+        let filtered = self.filtered_logs();
+        let counts = Self::group_counts(&filtered, key.0.as_deref());
+        let mut field_names = self.extraction_columns();
+        for entry in &filtered {
+            for (name, _) in &entry.fields {
+                if !field_names.contains(name) {
+                    field_names.push(name.clone());
+                }
+            }
+        }
+
+        if let Some(summary) = self.group_by.as_mut() {
+            summary.counts = counts;
+            summary.field_names = field_names;
+            summary.computed_for = Some(key);
+            summary.computed_at = Some(Instant::now());
+        }
+    }
+
+    /// Narrow the view to one group: a level selects the level filter,
+    /// a field value becomes a `field=value` search, quoted when it has to be
+    fn filter_to_group(&mut self, field: Option<String>, value: String) {
+        match field {
+            None => {
+                self.selected_log_levels = HashSet::from([value.to_lowercase()]);
+                self.current_level_filter = value;
+                self.filter_mode = FilterMode::IncludeSelected;
+            }
+            Some(name) => {
+                self.search_text = format!("{name}={}", Self::quote_field_value(&value));
+            }
+        }
+    }
+
+    fn show_group_by_window(&mut self, ctx: &egui::Context) {
+        self.refresh_group_counts(ctx);
+        let Some(summary) = self.group_by.as_mut() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut clicked_group = None;
+        egui::Window::new("Group By")
+            .open(&mut open)
+            .default_height(400.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Field:");
+                    egui::ComboBox::from_id_source("group_by_field")
+                        .selected_text(summary.field.as_deref().unwrap_or("detected level"))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut summary.field, None, "detected level");
+                            for name in &summary.field_names {
+                                ui.selectable_value(&mut summary.field, Some(name.clone()), name);
+                            }
+                        });
+                });
+                let total: usize = summary.counts.iter().map(|(_, count)| count).sum();
+                ui.weak(format!(
+                    "{} groups over {} filtered lines",
+                    Self::format_count(summary.counts.len()),
+                    Self::format_count(total)
+                ));
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        egui::Grid::new("group_by_counts")
+                            .striped(true)
+                            .num_columns(3)
+                            .show(ui, |ui| {
+                                ui.strong("Value");
+                                ui.strong("Lines");
+                                ui.strong("Share");
+                                ui.end_row();
+
+                                for (value, count) in &summary.counts {
+                                    match value {
+                                        Some(value) => {
+                                            if ui
+                                                .link(value)
+                                                .on_hover_text("Filter to this group")
+                                                .clicked()
+                                            {
+                                                clicked_group = Some(value.clone());
+                                            }
+                                        }
+                                        None => {
+                                            ui.weak("(none)");
+                                        }
+                                    }
+                                    ui.label(Self::format_count(*count));
+                                    ui.label(format!(
+                                        "{:.1}%",
+                                        *count as f64 * 100.0 / total.max(1) as f64
+                                    ));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+
+        let field = summary.field.clone();
+        if !open {
+            self.group_by = None;
+        }
+        if let Some(value) = clicked_group {
+            self.filter_to_group(field, value);
+        }
+    }
+
+    /// Whether `program` is an executable in one of the `PATH` directories
+    fn on_path(program: &str) -> bool {
+        std::env::var_os("PATH").is_some_and(|paths| {
+            std::env::split_paths(&paths).any(|dir| {
+                let path = dir.join(program);
+                path.is_file() || path.with_extension("exe").is_file()
+            })
+        })
+    }
+
+    /// `docker ps --format '{{.ID}} {{.Names}}'` output as (id, name) pairs
+    fn parse_docker_ps(output: &str) -> Vec<(String, String)> {
+        output
+            .lines()
+            .filter_map(|line| line.trim().split_once(char::is_whitespace))
+            .map(|(id, name)| (id.to_string(), name.trim().to_string()))
+            .collect()
+    }
+
+    fn open_docker_picker(&mut self) {
+        let (tx, rx) = mpsc::channel();
+        Self::spawn_for_output(
+            "docker".to_string(),
+            vec![
+                "ps".to_string(),
+                "--format".to_string(),
+                "{{.ID}} {{.Names}}".to_string(),
+            ],
+            tx,
+            self.egui_ctx.clone(),
+        );
+        let picker = self.docker_picker.get_or_insert_with(DockerPicker::default);
+        picker.pending = Some(rx);
+    }
+
+    /// Make `command` the active tab's source, run locally, and start it
+    fn follow_command(&mut self, command: String) {
+        let source = self.source_mut();
+        source.command = command;
+        source.follow_file = false;
+        source.ssh = None;
+        self.save_settings();
+        self.restart_log_collection();
+    }
+
+    fn show_docker_picker(&mut self, ctx: &egui::Context) {
+        let Some(picker) = self.docker_picker.as_mut() else {
+            return;
+        };
+        let mut error = None;
+        if let Some(result) = picker.pending.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            picker.pending = None;
+            match result {
+                Ok(output) => {
+                    picker.containers = Self::parse_docker_ps(&output);
+                    picker.selected = (!picker.containers.is_empty()).then_some(0);
+                }
+                Err(message) => error = Some(message),
+            }
+        }
+
+        let mut open = true;
+        let mut refresh = false;
+        let mut follow = None;
+        egui::Window::new("Docker Containers")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let label = |(id, name): &(String, String)| format!("{name} ({id})");
+                    egui::ComboBox::from_id_source("docker_container")
+                        .width(260.0)
+                        .selected_text(
+                            picker
+                                .selected
+                                .and_then(|index| picker.containers.get(index))
+                                .map_or("No running containers".to_string(), label),
+                        )
+                        .show_ui(ui, |ui| {
+                            for (index, container) in picker.containers.iter().enumerate() {
+                                ui.selectable_value(
+                                    &mut picker.selected,
+                                    Some(index),
+                                    label(container),
+                                );
+                            }
+                        });
+                    if picker.pending.is_some() {
+                        ui.spinner();
+                    } else if ui.button("🔄").on_hover_text("Refresh").clicked() {
+                        refresh = true;
+                    }
+                });
+                let selected = picker
+                    .selected
+                    .and_then(|index| picker.containers.get(index));
+                if ui
+                    .add_enabled(selected.is_some(), egui::Button::new("Follow logs"))
+                    .clicked()
+                {
+                    follow = selected.map(|(id, _)| id.clone());
+                }
+            });
+
+        if let Some(message) = error {
+            self.notify_error(message);
+        }
+        if refresh {
+            self.open_docker_picker();
+        }
+        if let Some(id) = follow {
+            self.docker_picker = None;
+            // With some recent history
+            self.follow_command(format!("docker logs -f --tail 200 {id}"));
+        } else if !open {
+            self.docker_picker = None;
+        }
+    }
+
+    /// `kubectl` arguments, scoped to `namespace` unless it's empty
+    fn kubectl_args(namespace: &str, args: &[&str]) -> Vec<String> {
+        let mut all: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        if !namespace.trim().is_empty() {
+            all.extend(["-n".to_string(), namespace.trim().to_string()]);
+        }
+        all
+    }
+
+    /// Command following a pod's logs; `container` picks one of several
+    fn kubectl_logs_command(pod: &str, namespace: &str, container: Option<&str>) -> String {
+        let mut parts = vec!["kubectl", "logs", "-f", pod];
+        if !namespace.trim().is_empty() {
+            parts.extend(["-n", namespace.trim()]);
+        }
+        if let Some(container) = container {
+            parts.extend(["-c", container]);
+        }
+        parts.join(" ")
+    }
+
+    fn load_pods(&mut self) {
+        let Some(picker) = self.pod_picker.as_mut() else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel();
+        Self::spawn_for_output(
+            "kubectl".to_string(),
+            Self::kubectl_args(&picker.namespace, &["get", "pods", "-o", "name"]),
+            tx,
+            self.egui_ctx.clone(),
+        );
+        picker.pending_pods = Some(rx);
+        picker.namespace_edited = false;
+    }
+
+    fn load_containers(&mut self) {
+        let Some(picker) = self.pod_picker.as_mut() else {
+            return;
+        };
+        picker.containers.clear();
+        picker.selected_container = None;
+        let Some(pod) = picker.selected_pod.and_then(|index| picker.pods.get(index)) else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel();
+        Self::spawn_for_output(
+            "kubectl".to_string(),
+            Self::kubectl_args(
+                &picker.namespace,
+                &[
+                    "get",
+                    "pod",
+                    pod.as_str(),
+                    "-o",
+                    "jsonpath={.spec.containers[*].name}",
+                ],
+            ),
+            tx,
+            self.egui_ctx.clone(),
+        );
+        picker.pending_containers = Some(rx);
+    }
+
+    fn show_pod_picker(&mut self, ctx: &egui::Context) {
+        let Some(picker) = self.pod_picker.as_mut() else {
+            return;
+        };
+        let mut error = None;
+        let mut pods_loaded = false;
+        if let Some(result) = picker
+            .pending_pods
+            .as_ref()
+            .and_then(|rx| rx.try_recv().ok())
+        {
+            picker.pending_pods = None;
+            match result {
+                Ok(output) => {
+                    // `-o name` prints `pod/<name>`
+                    picker.pods = output
+                        .lines()
+                        .map(|line| line.trim().trim_start_matches("pod/").to_string())
+                        .filter(|pod| !pod.is_empty())
+                        .collect();
+                    picker.selected_pod = (!picker.pods.is_empty()).then_some(0);
+                    pods_loaded = true;
+                }
+                Err(message) => error = Some(message),
+            }
+        }
+        if let Some(result) = picker
+            .pending_containers
+            .as_ref()
+            .and_then(|rx| rx.try_recv().ok())
+        {
+            picker.pending_containers = None;
+            match result {
+                Ok(output) => {
+                    picker.containers = output.split_whitespace().map(str::to_string).collect();
+                }
+                Err(message) => error = Some(message),
+            }
+        }
+
+        let mut open = true;
+        let mut reload_pods = false;
+        let mut pod_changed = false;
+        let mut follow = None;
+        egui::Window::new("Kubernetes Pods")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("pod_picker").show(ui, |ui| {
+                    ui.label("Namespace:");
+                    ui.horizontal(|ui| {
+                        let response = ui.add(
+                            egui::TextEdit::singleline(&mut picker.namespace)
+                                .hint_text("current context")
+                                .desired_width(160.0),
+                        );
+                        picker.namespace_edited |= response.changed();
+                        if response.lost_focus() && picker.namespace_edited {
+                            reload_pods = true;
+                        }
+                        if picker.pending_pods.is_some() {
+                            ui.spinner();
+                        } else if ui.button("🔄").on_hover_text("Refresh").clicked() {
+                            reload_pods = true;
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Pod:");
+                    egui::ComboBox::from_id_source("pod_picker_pod")
+                        .width(260.0)
+                        .selected_text(
+                            picker
+                                .selected_pod
+                                .and_then(|index| picker.pods.get(index))
+                                .map_or("No pods", String::as_str),
+                        )
+                        .show_ui(ui, |ui| {
+                            for (index, pod) in picker.pods.iter().enumerate() {
+                                pod_changed |= ui
+                                    .selectable_value(&mut picker.selected_pod, Some(index), pod)
+                                    .changed();
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Container:");
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_source("pod_picker_container")
+                            .width(260.0)
+                            .selected_text(
+                                picker
+                                    .selected_container
+                                    .and_then(|index| picker.containers.get(index))
+                                    .map_or("default", String::as_str),
+                            )
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(
+                                    &mut picker.selected_container,
+                                    None,
+                                    "default",
+                                );
+                                for (index, container) in picker.containers.iter().enumerate() {
+                                    ui.selectable_value(
+                                        &mut picker.selected_container,
+                                        Some(index),
+                                        container,
+                                    );
+                                }
+                            });
+                        if picker.pending_containers.is_some() {
+                            ui.spinner();
+                        }
+                    });
+                    ui.end_row();
+                });
+
+                let pod = picker.selected_pod.and_then(|index| picker.pods.get(index));
+                if ui
+                    .add_enabled(pod.is_some(), egui::Button::new("Follow logs"))
+                    .clicked()
+                    && let Some(pod) = pod
+                {
+                    let container = picker
+                        .selected_container
+                        .and_then(|index| picker.containers.get(index))
+                        .map(String::as_str);
+                    follow = Some(Self::kubectl_logs_command(
+                        pod,
+                        &picker.namespace,
+                        container,
+                    ));
+                }
+            });
+
+        if let Some(message) = error {
+            self.notify_error(message);
+        }
+        if reload_pods {
+            self.load_pods();
+        } else if pods_loaded || pod_changed {
+            self.load_containers();
+        }
+        if let Some(command) = follow {
+            self.pod_picker = None;
+            self.follow_command(command);
+        } else if !open {
+            self.pod_picker = None;
+        }
+    }
+
+    fn show_glob_window(&mut self, ctx: &egui::Context) {
+        let Some(pattern) = self.glob_pattern_input.as_mut() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut submit = false;
+        egui::Window::new("Open Files Matching")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("Glob pattern, e.g. /var/log/app/*.log or /var/log/app/*.gz");
+                let response = ui.text_edit_singleline(pattern);
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    submit = true;
+                }
+                if ui.button("Open").clicked() {
+                    submit = true;
+                }
+            });
+
+        if submit {
+            let pattern = self.glob_pattern_input.take().unwrap_or_default();
+            self.open_log_glob(pattern.trim());
+        } else if !open {
+            self.glob_pattern_input = None;
+        }
+    }
+
+    /// Flatten a pasted multi-line command (including `\` line continuations)
+    /// into the single line that is actually run
+    fn effective_command(command: &str) -> String {
+        command
+            .lines()
+            .map(|line| line.trim().trim_end_matches('\\').trim_end())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn get_time_range(&self) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        let filter = &self.settings.time_filter;
+        match &filter.mode {
+            TimeSpanMode::Disabled => None,
+            TimeSpanMode::Predefined(span) => {
+                let now = Local::now().naive_local();
+                let duration = span.to_duration();
+                let from = now - duration;
+                Some((from, now))
+            }
+            TimeSpanMode::Custom => {
+                let from = NaiveDate::from_ymd_opt(
+                    filter.custom_from_year,
+                    filter.custom_from_month,
+                    filter.custom_from_day,
+                )?
+                .and_time(NaiveTime::from_hms_opt(
+                    filter.custom_from_hour,
+                    filter.custom_from_minute,
+                    0,
+                )?);
+
+                let to = NaiveDate::from_ymd_opt(
+                    filter.custom_to_year,
+                    filter.custom_to_month,
+                    filter.custom_to_day,
+                )?
+                .and_time(NaiveTime::from_hms_opt(
+                    filter.custom_to_hour,
+                    filter.custom_to_minute,
+                    59,
+                )?);
+
+                Some((from, to))
+            }
+            TimeSpanMode::Relative => {
+                let now = Local::now().naive_local();
+                let duration = filter
+                    .relative_unit
+                    .to_duration(filter.relative_amount as i64);
+                let from = now - duration;
+                Some((from, now))
+            }
+        }
+    }
+
+    fn parse_time_input(input: &str) -> Option<NaiveDateTime> {
+        if input.trim().is_empty() {
+            return None;
+        }
+
+        let trimmed = input.trim();
+
+        // Try full format first: "2025-09-15 12:23:30"
+        if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M:%S") {
+            return Some(dt);
+        }
+
+        // Try date + hour:minute: "2025-09-15 12:23"
+        if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M") {
+            return Some(dt);
+        }
+
+        // Try date + hour: "2025-09-15 12"
+        if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H") {
+            return Some(dt);
+        }
+
+        // Try just date: "2025-09-15"
+        if let Ok(date) = NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
+            return Some(date.and_time(NaiveTime::from_hms_opt(0, 0, 0)?));
+        }
+
+        None
+    }
+
+    /// Syslog timestamps carry no year, so pick the one that doesn't put the
+    /// month after `now`'s (December lines read in January belong to last year)
+    fn parse_syslog_timestamp(
+        timestamp: &str,
+        format: &str,
+        now: NaiveDateTime,
+    ) -> Option<NaiveDateTime> {
+        let parse = |year: i32| {
+            NaiveDateTime::parse_from_str(&format!("{year} {timestamp}"), &format!("%Y {format}"))
+                .ok()
+        };
+
+        // 2000 is a leap year, so even "Feb 29" yields its month here
+        let month = parse(2000)?.month();
+        let year = if month > now.month() {
+            now.year() - 1
+        } else {
+            now.year()
+        };
+        parse(year)
     }
 
     fn extract_timestamp_from_log(content: &str) -> (Option<String>, String) {
@@ -370,142 +2630,2993 @@ impl LogsApp {
             (r"(\d{10})", "unix"),
         ];
 
-        for (pattern, format) in &patterns {
-            if let Ok(re) = Regex::new(pattern) {
-                if let Some(captures) = re.captures(content) {
-                    if let Some(timestamp_match) = captures.get(1) {
-                        let timestamp_str = timestamp_match.as_str();
-
-                        // Parse the timestamp
-                        let parsed_timestamp = if *format == "unix" {
-                            // Handle Unix timestamp
-                            if let Ok(unix_ts) = timestamp_str.parse::<i64>() {
-                                chrono::DateTime::from_timestamp(unix_ts, 0)
-                                    .map(|dt| dt.naive_local())
-                            } else {
-                                None
-                            }
-                        } else if format.contains("%b") {
-                            // Handle syslog format - need to add current year
-                            let current_year = Local::now().year();
-                            let with_year = format!("{current_year} {timestamp_str}");
-                            NaiveDateTime::parse_from_str(&with_year, &format!("%Y {format}")).ok()
-                        } else {
-                            // Handle other formats
-                            NaiveDateTime::parse_from_str(timestamp_str, format).ok()
-                        };
+        // Every parseable match is a candidate. Patterns carrying a date win over
+        // loose time-only or epoch matches; among those the leftmost match wins,
+        // then the longest, then the earlier pattern.
+        let mut best: Option<((bool, usize, usize, usize), NaiveDateTime, Range<usize>)> = None;
+        for (index, (pattern, format)) in patterns.iter().enumerate() {
+            let Ok(re) = Regex::new(pattern) else {
+                continue;
+            };
+            let dated = format.contains("%d");
+            for captures in re.captures_iter(content) {
+                let Some(timestamp_match) = captures.get(1) else {
+                    continue;
+                };
+                let timestamp_str = timestamp_match.as_str();
+
+                // Parse the timestamp
+                let parsed_timestamp = if *format == "unix" {
+                    // Handle Unix timestamp
+                    timestamp_str
+                        .parse::<i64>()
+                        .ok()
+                        .and_then(|unix_ts| chrono::DateTime::from_timestamp(unix_ts, 0))
+                        .map(|dt| dt.naive_local())
+                } else if format.contains("%b") {
+                    // Handle syslog format - need to add the year
+                    Self::parse_syslog_timestamp(timestamp_str, format, Local::now().naive_local())
+                } else {
+                    // Handle other formats
+                    NaiveDateTime::parse_from_str(timestamp_str, format).ok()
+                };
+                let Some(dt) = parsed_timestamp else {
+                    continue;
+                };
+
+                let rank = (
+                    !dated,
+                    timestamp_match.start(),
+                    usize::MAX - timestamp_match.len(),
+                    index,
+                );
+                if best
+                    .as_ref()
+                    .is_none_or(|(best_rank, _, _)| rank < *best_rank)
+                {
+                    best = Some((rank, dt, timestamp_match.range()));
+                }
+            }
+        }
+
+        if let Some((_, dt, range)) = best {
+            let formatted_timestamp = dt.format("%Y-%m-%d %H:%M:%S").to_string();
+            // Remove the timestamp from content to avoid duplication
+            let mut cleaned_content = content.to_string();
+            cleaned_content.replace_range(range, "");
+            return (
+                Some(formatted_timestamp),
+                cleaned_content.trim().to_string(),
+            );
+        }
+
+        // No timestamp found, return original content
+        (None, content.to_string())
+    }
+
+    /// Fast path for formats that lead with a timestamp token: parse everything
+    /// up to the first space with `format`, and keep the rest as content
+    fn first_token_timestamp(content: &str, format: &str) -> Option<(Option<String>, String)> {
+        let trimmed = content.trim_start();
+        let (token, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+        let parsed = NaiveDateTime::parse_from_str(token, format).ok()?;
+        Some((
+            Some(parsed.format("%Y-%m-%d %H:%M:%S").to_string()),
+            rest.trim().to_string(),
+        ))
+    }
+
+    fn compile_extraction_rules(&mut self) {
+        self.extraction_patterns = self
+            .settings
+            .extraction_rules
+            .iter()
+            .filter_map(|rule| Regex::new(rule).ok())
+            .collect();
+    }
+
+    fn compile_color_rules(&mut self) {
+        self.compiled_color_rules = self
+            .settings
+            .color_rules
+            .iter()
+            .filter_map(|rule| {
+                let [r, g, b] = rule.color;
+                Some((
+                    ColorMatcher::parse(&rule.matcher)?,
+                    egui::Color32::from_rgb(r, g, b),
+                ))
+            })
+            .collect();
+    }
+
+    /// Text color from the first color rule matching the entry, if any
+    fn rule_color(&self, entry: &LogEntry) -> Option<egui::Color32> {
+        self.compiled_color_rules
+            .iter()
+            .find(|(matcher, _)| match matcher {
+                ColorMatcher::Field(query) => Self::match_field_query(entry, query) == Some(true),
+                ColorMatcher::Pattern(regex) => regex.is_match(&entry.content),
+            })
+            .map(|(_, color)| *color)
+    }
+
+    fn compile_level_pattern(&mut self) {
+        let pattern = self.settings.level_pattern.trim();
+        self.level_regex = if pattern.is_empty() {
+            None
+        } else {
+            Regex::new(pattern)
+                .ok()
+                .filter(|regex| regex.capture_names().flatten().any(|name| name == "level"))
+        };
+    }
+
+    fn compile_split_pattern(&mut self) {
+        let pattern = self.settings.split_pattern.trim();
+        self.split_regex = if pattern.is_empty() {
+            None
+        } else {
+            Regex::new(pattern)
+                .ok()
+                .filter(|regex| regex.captures_len() > 1)
+        };
+    }
+
+    /// Group lines by the first capture of the split pattern, keys in order of
+    /// first appearance. Lines that don't match, and keys beyond the first
+    /// `MAX_SPLIT_STREAMS - 1`, go to a trailing "other" stream.
+    fn split_streams<'a>(regex: &Regex, logs: &[&'a LogEntry]) -> Vec<(String, Vec<&'a LogEntry>)> {
+        let mut streams: Vec<(String, Vec<&'a LogEntry>)> = Vec::new();
+        let mut unmatched = Vec::new();
+        for &entry in logs {
+            let key = regex
+                .captures(&entry.content)
+                .and_then(|captures| captures.iter().skip(1).flatten().next())
+                .map(|group| group.as_str());
+            let Some(key) = key else {
+                unmatched.push(entry);
+                continue;
+            };
+            match streams.iter_mut().find(|(name, _)| name == key) {
+                Some((_, entries)) => entries.push(entry),
+                None if streams.len() < MAX_SPLIT_STREAMS - 1 => {
+                    streams.push((key.to_string(), vec![entry]))
+                }
+                None => unmatched.push(entry),
+            }
+        }
+        if !unmatched.is_empty() {
+            streams.push(("other".to_string(), unmatched));
+        }
+        streams
+    }
+
+    /// One column per stream; returns the id of a clicked line
+    fn show_split_streams(
+        &self,
+        ui: &mut egui::Ui,
+        regex: &Regex,
+        logs: &[&LogEntry],
+    ) -> Option<u64> {
+        let streams = Self::split_streams(regex, logs);
+        if streams.is_empty() {
+            ui.weak("No lines to split yet");
+            return None;
+        }
+
+        let mut clicked = None;
+        let colorize_levels = self.colorize_levels();
+        let show_markers = !colorize_levels || self.settings.theme == Theme::HighContrast;
+        ui.columns(streams.len(), |columns| {
+            for (ui, (name, entries)) in columns.iter_mut().zip(&streams) {
+                ui.horizontal(|ui| {
+                    ui.strong(name);
+                    ui.weak(format!("({})", entries.len()));
+                });
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .id_source(("split_stream", name))
+                    .auto_shrink([false, false])
+                    .stick_to_bottom(self.auto_scroll)
+                    .show(ui, |ui| {
+                        for entry in entries {
+                            ui.horizontal(|ui| {
+                                if let Some(level) = entry.level {
+                                    ui.label(Self::level_chip(
+                                        level,
+                                        colorize_levels,
+                                        show_markers,
+                                    ));
+                                }
+                                let mut text = egui::RichText::new(&entry.content);
+                                if self.selected_entry == Some(entry.id) {
+                                    text = text.background_color(ui.visuals().selection.bg_fill);
+                                }
+                                if ui
+                                    .add(
+                                        egui::Label::new(text)
+                                            .truncate()
+                                            .sense(egui::Sense::click()),
+                                    )
+                                    .on_hover_text(&entry.timestamp)
+                                    .clicked()
+                                {
+                                    clicked = Some(entry.id);
+                                }
+                            });
+                        }
+                    });
+            }
+        });
+        clicked
+    }
+
+    /// Names of the columns produced by the extraction rules, in rule order
+    fn extraction_columns(&self) -> Vec<String> {
+        let mut columns: Vec<String> = Vec::new();
+        if self.settings.raw_mode {
+            return columns;
+        }
+        for pattern in &self.extraction_patterns {
+            for name in pattern.capture_names().flatten() {
+                if !columns.iter().any(|column| column == name) {
+                    columns.push(name.to_string());
+                }
+            }
+        }
+        columns
+    }
+
+    /// Match a `field=value` or numeric comparison (`latency>200`,
+    /// `status>=500`) query against an entry's structured fields. Returns
+    /// `None` when the query isn't of that form, or for an equality query on
+    /// an entry lacking the field, so the caller can fall back to text search.
+    fn match_field_query(entry: &LogEntry, query: &str) -> Option<bool> {
+        let captures = FIELD_QUERY_PATTERN.captures(query)?;
+        let name = &captures[1];
+        let operator = &captures[2];
+        let expected = &Self::unquote_field_value(&captures[3]);
+
+        match operator {
+            "=" => {
+                let value = entry.field(name)?;
+                Some(value.eq_ignore_ascii_case(expected))
+            }
+            "!=" => Some(
+                entry
+                    .field(name)
+                    .is_some_and(|value| !value.eq_ignore_ascii_case(expected)),
+            ),
+            _ => {
+                let expected = Self::parse_number(expected)?;
+                // Missing or non-numeric values fail the comparison
+                let Some(value) = entry.field(name).and_then(Self::parse_number) else {
+                    return Some(false);
+                };
+                Some(match operator {
+                    ">" => value > expected,
+                    ">=" => value >= expected,
+                    "<" => value < expected,
+                    _ => value <= expected,
+                })
+            }
+        }
+    }
+
+    /// Wrap a value that's empty, has whitespace or quotes in double quotes,
+    /// escaping `"` and `\\`, so a field query reads it back unchanged
+    fn quote_field_value(value: &str) -> String {
+        if !value.is_empty() && !value.contains(|c: char| c.is_whitespace() || c == '"') {
+            return value.to_string();
+        }
+        let mut quoted = String::with_capacity(value.len() + 2);
+        quoted.push('"');
+        for c in value.chars() {
+            if c == '"' || c == '\\' {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        }
+        quoted.push('"');
+        quoted
+    }
+
+    /// The value of a field query, with the quotes and escapes of a double
+    /// quoted one removed
+    fn unquote_field_value(value: &str) -> String {
+        let Some(inner) = value
+            .strip_prefix('"')
+            .and_then(|inner| inner.strip_suffix('"'))
+        else {
+            return value.to_string();
+        };
+        let mut unquoted = String::with_capacity(inner.len());
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            unquoted.push(if c == '\\' {
+                chars.next().unwrap_or(c)
+            } else {
+                c
+            });
+        }
+        unquoted
+    }
+
+    /// Parse a field value as a number, ignoring a trailing unit like `ms`
+    fn parse_number(value: &str) -> Option<f64> {
+        value
+            .trim()
+            .trim_end_matches(|c: char| c.is_alphabetic() || c == '%')
+            .parse()
+            .ok()
+    }
+
+    /// Extract structured fields from a JSON object or logfmt (`key=value`) line
+    fn parse_fields(content: &str) -> Vec<(String, String)> {
+        let trimmed = content.trim();
+        if trimmed.starts_with('{')
+            && let Ok(serde_json::Value::Object(map)) =
+                serde_json::from_str::<serde_json::Value>(trimmed)
+        {
+            return map
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        serde_json::Value::String(text) => text,
+                        other => other.to_string(),
+                    };
+                    (key, value)
+                })
+                .collect();
+        }
+
+        LOGFMT_PATTERN
+            .captures_iter(content)
+            .map(|captures| {
+                let value = captures[2]
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .unwrap_or(&captures[2]);
+                (captures[1].to_string(), value.to_string())
+            })
+            .collect()
+    }
+
+    /// Start collecting for the source in tab `tab`, unless it already is
+    fn start_log_collection(&mut self, tab: usize) {
+        let source = &mut self.tabs[tab];
+        if source.log_thread_handle.is_some() {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        source.log_receiver = Some(rx);
+        source.is_loading = true;
+        source.collection_state = CollectionState::Tailing;
+        source.last_log_arrival = Some(Instant::now());
+        source.collection_started = Some(Instant::now());
+        source.spawned_at = None;
+        source.process_status = None;
+        source.bytes_received = Arc::new(AtomicU64::new(0));
+        let bytes_received = Arc::clone(&source.bytes_received);
+
+        let command = Self::effective_command(&source.command);
+        source.running_command = Some(command.clone());
+        source.last_error = None;
+        if source.follow_file {
+            let path = PathBuf::from(command.trim());
+            let path = match &self.settings.working_dir {
+                Some(dir) => dir.join(path),
+                None => path,
+            };
+            source.log_child = None;
+            source.collection_stop = Arc::new(AtomicBool::new(false));
+            let stop = Arc::clone(&source.collection_stop);
+            let repaint_ctx = self.egui_ctx.clone();
+            let encoding = self.settings.encoding;
+            source.log_thread_handle = Some(thread::spawn(move || {
+                Self::follow_file(
+                    &path,
+                    &tx,
+                    &stop,
+                    repaint_ctx.as_ref(),
+                    encoding,
+                    &bytes_received,
+                );
+            }));
+            return;
+        }
+
+        // History only goes into an empty buffer, so reconnects don't repeat it
+        let command = if source.logs.is_empty() {
+            Self::with_backlog(&command, self.settings.backlog_lines).unwrap_or(command)
+        } else {
+            command
+        };
+        let shared_child = Arc::new(Mutex::new(None));
+        source.log_child = Some(Arc::clone(&shared_child));
+        source.collection_stop = Arc::new(AtomicBool::new(false));
+        let stop = Arc::clone(&source.collection_stop);
+        let repaint_ctx = self.egui_ctx.clone();
+        let encoding = self.settings.encoding;
+        let use_shell = self.settings.use_shell;
+        let env: Vec<(String, String)> = self
+            .settings
+            .env
+            .iter()
+            // Rows flagged invalid in the editor are skipped
+            .filter(|(key, _)| !key.trim().is_empty() && !key.contains('='))
+            .map(|(key, value)| (key.trim().to_string(), value.clone()))
+            .collect();
+        let working_dir = self.settings.working_dir.clone();
+        let ssh = source.ssh.clone();
+
+        let handle = thread::spawn(move || {
+            let process = match &ssh {
+                Some(target) => Self::ssh_process(target, &command, &env, working_dir.as_deref()),
+                None => Self::collection_process(&command, use_shell),
+            };
+            let mut cmd = match process {
+                Ok(cmd) => cmd,
+                Err(message) => {
+                    let _ = tx.send(CollectorMessage::SpawnFailed(message));
+                    return;
+                }
+            };
+            let program = cmd.get_program().to_string_lossy().into_owned();
+            cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+            // Over ssh both are part of the remote command instead
+            if ssh.is_none() {
+                cmd.envs(env);
+            }
+            if ssh.is_none()
+                && let Some(dir) = working_dir
+            {
+                // Spawning would fail with a bare "not found" that blames the program
+                if !dir.is_dir() {
+                    let _ = tx.send(CollectorMessage::SpawnFailed(format!(
+                        "Working directory {} doesn't exist",
+                        dir.display()
+                    )));
+                    return;
+                }
+                cmd.current_dir(dir);
+            }
+            // Its own process group, so stopping reaches everything a shell started
+            #[cfg(unix)]
+            std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+
+            match cmd.spawn() {
+                Ok(mut child) => {
+                    let stdout = child.stdout.take();
+                    let stderr = child.stderr.take();
+                    match shared_child.lock() {
+                        Ok(mut slot) if !stop.load(Ordering::Relaxed) => {
+                            *slot = Some(child);
+                            let _ = tx.send(CollectorMessage::Started);
+                        }
+                        // Stopped while spawning; nobody else can reach this child
+                        _ => {
+                            let _ = child.kill();
+                            let _ = child.wait();
+                            return;
+                        }
+                    }
+
+                    let stderr_handle = stderr.map(|stderr| {
+                        let reader = CountingReader {
+                            inner: stderr,
+                            count: Arc::clone(&bytes_received),
+                        };
+                        let tx = tx.clone();
+                        let repaint_ctx = repaint_ctx.clone();
+                        thread::spawn(move || {
+                            Self::forward_lines(
+                                reader,
+                                encoding,
+                                &tx,
+                                repaint_ctx.as_ref(),
+                                CollectorMessage::StderrLine,
+                            );
+                        })
+                    });
+                    if let Some(stdout) = stdout {
+                        let reader = CountingReader {
+                            inner: stdout,
+                            count: bytes_received,
+                        };
+                        Self::forward_lines(
+                            reader,
+                            encoding,
+                            &tx,
+                            repaint_ctx.as_ref(),
+                            CollectorMessage::Line,
+                        );
+                    }
+
+                    // A stopped command may hold stderr open, so kill it before
+                    // waiting for the stderr reader to drain
+                    if stop.load(Ordering::Relaxed)
+                        && let Ok(mut slot) = shared_child.lock()
+                        && let Some(child) = slot.as_mut()
+                    {
+                        let _ = child.kill();
+                    }
+                    if let Some(handle) = stderr_handle {
+                        let _ = handle.join();
+                    }
+
+                    // Clean up the child process, unless it was already reaped on exit.
+                    // A child still running after a stop would make `wait` block forever.
+                    let child = shared_child.lock().ok().and_then(|mut slot| slot.take());
+                    if let Some(mut child) = child {
+                        if stop.load(Ordering::Relaxed) {
+                            let _ = child.kill();
+                        }
+                        let status = child.wait();
+                        if !stop.load(Ordering::Relaxed)
+                            && let Ok(status) = status
+                        {
+                            let _ = tx.send(CollectorMessage::Exited(status.code()));
+                        }
+                    }
+                }
+                Err(err) => {
+                    let _ = tx.send(CollectorMessage::SpawnFailed(format!(
+                        "Failed to start `{program}`: {err}"
+                    )));
+                }
+            }
+        });
+
+        source.log_thread_handle = Some(handle);
+    }
+
+    /// Split a command into program and arguments like a shell would,
+    /// honoring single and double quotes and backslash escapes
+    fn command_args(command: &str) -> Result<Vec<String>, String> {
+        let args = shlex::split(command)
+            .ok_or_else(|| "Unbalanced quotes or trailing backslash in the command".to_string())?;
+        if args.is_empty() {
+            return Err("No command given".to_string());
+        }
+        Ok(args)
+    }
+
+    /// Read lines piped into the app in place of running the command. The
+    /// spinner clears on the first line like it does for a command.
+    fn start_stdin_collection(&mut self) {
+        let (tx, rx) = mpsc::channel();
+        let source = &mut self.tabs[self.active_tab];
+        source.log_receiver = Some(rx);
+        source.is_loading = true;
+        source.collection_state = CollectionState::Tailing;
+        source.last_log_arrival = Some(Instant::now());
+        source.collection_started = Some(Instant::now());
+        source.running_command = Some(STDIN_LABEL.to_string());
+        source.reading_stdin = true;
+        source.bytes_received = Arc::new(AtomicU64::new(0));
+        let bytes_received = Arc::clone(&source.bytes_received);
+        let repaint_ctx = self.egui_ctx.clone();
+        let encoding = self.settings.encoding;
+
+        source.log_thread_handle = Some(thread::spawn(move || {
+            let reader = CountingReader {
+                inner: std::io::stdin().lock(),
+                count: bytes_received,
+            };
+            Self::forward_lines(
+                reader,
+                encoding,
+                &tx,
+                repaint_ctx.as_ref(),
+                CollectorMessage::Line,
+            );
+        }));
+    }
+
+    /// Follow a file like `tail -f`: start at its end and poll for appended
+    /// bytes. A file that shrank was truncated, and a different file at the
+    /// path means it was rotated, so either way it's reopened and read from
+    /// the start.
+    fn follow_file(
+        path: &Path,
+        tx: &mpsc::Sender<CollectorMessage>,
+        stop: &AtomicBool,
+        repaint_ctx: Option<&egui::Context>,
+        encoding: LogEncoding,
+        bytes_received: &AtomicU64,
+    ) {
+        let opened = fs::File::open(path)
+            .and_then(|mut file| file.seek(SeekFrom::End(0)).map(|offset| (file, offset)));
+        let (mut file, mut offset) = match opened {
+            Ok(opened) => opened,
+            Err(err) => {
+                let _ = tx.send(CollectorMessage::SpawnFailed(format!(
+                    "Failed to open {}: {err}",
+                    path.display()
+                )));
+                return;
+            }
+        };
+        let _ = tx.send(CollectorMessage::Started);
+
+        let mut identity = file
+            .metadata()
+            .ok()
+            .and_then(|metadata| Self::file_identity(&metadata));
+        let mut pending = Vec::new();
+        while !stop.load(Ordering::Relaxed) {
+            if fs::metadata(path).is_ok_and(|metadata| {
+                metadata.len() < offset || Self::file_identity(&metadata) != identity
+            }) && let Ok(reopened) = fs::File::open(path)
+            {
+                identity = reopened
+                    .metadata()
+                    .ok()
+                    .and_then(|metadata| Self::file_identity(&metadata));
+                file = reopened;
+                offset = 0;
+                pending.clear();
+            }
+
+            let start = pending.len();
+            let read = file
+                .seek(SeekFrom::Start(offset))
+                .and_then(|_| file.read_to_end(&mut pending));
+            if let Ok(read) = read
+                && read > 0
+            {
+                offset += read as u64;
+                bytes_received.fetch_add(read as u64, Ordering::Relaxed);
+                // A trailing partial line waits for the rest to be written
+                let complete = pending[start..]
+                    .iter()
+                    .rposition(|&byte| byte == b'\n')
+                    .map_or(0, |index| start + index + 1);
+                for line in pending[..complete].split_inclusive(|&byte| byte == b'\n') {
+                    if tx
+                        .send(CollectorMessage::Line(encoding.decode_line(line)))
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+                pending.drain(..complete);
+                if let Some(ctx) = repaint_ctx {
+                    ctx.request_repaint();
+                }
+            }
+            thread::sleep(std::time::Duration::from_millis(FOLLOW_POLL_MS));
+        }
+    }
+
+    /// The device and inode behind a path, which change when a file is
+    /// rotated even if the new one has already grown past the old offset
+    #[cfg(unix)]
+    fn file_identity(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+        use std::os::unix::fs::MetadataExt;
+        Some((metadata.dev(), metadata.ino()))
+    }
+
+    /// Without a portable file identity, rotation shows up only as shrinking
+    #[cfg(not(unix))]
+    fn file_identity(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+        None
+    }
+
+    /// An `ssh` invocation running `command` on `target`. The command goes
+    /// over as a single argument, so the remote shell sees it unchanged, after
+    /// changing to `working_dir` and exporting `env` there.
+    fn ssh_process(
+        target: &SshTarget,
+        command: &str,
+        env: &[(String, String)],
+        working_dir: Option<&Path>,
+    ) -> Result<Command, String> {
+        let host = target.host.trim();
+        let user = target.user.trim();
+        if host.is_empty() {
+            return Err("No ssh host given".to_string());
+        }
+        // Anything starting with a dash would be read as an ssh option
+        if host.starts_with('-') || user.starts_with('-') {
+            return Err(format!("Invalid ssh destination {user}@{host}"));
+        }
+        if command.trim().is_empty() {
+            return Err("No remote command given".to_string());
+        }
+
+        let remote_command = Self::remote_command(command, env, working_dir)?;
+        let destination = if user.is_empty() {
+            host.to_string()
+        } else {
+            format!("{user}@{host}")
+        };
+        let mut process = Command::new("ssh");
+        // Never wait on a password prompt nobody can see, and notice dead connections
+        process
+            .args(["-o", "BatchMode=yes", "-o", "ServerAliveInterval=15"])
+            .args(["-p", &target.port.to_string()])
+            .arg(destination)
+            .arg(remote_command);
+        Ok(process)
+    }
+
+    /// `command` prefixed with a `cd` and `export` for the remote shell, with
+    /// every value quoted
+    fn remote_command(
+        command: &str,
+        env: &[(String, String)],
+        working_dir: Option<&Path>,
+    ) -> Result<String, String> {
+        let quote = |text: &str| {
+            shlex::try_quote(text)
+                .map(|quoted| quoted.into_owned())
+                .map_err(|_| format!("Can't pass {text:?} to the remote shell"))
+        };
+        let mut steps = Vec::new();
+        if let Some(dir) = working_dir {
+            steps.push(format!("cd {}", quote(&dir.to_string_lossy())?));
+        }
+        if !env.is_empty() {
+            let mut assignments = Vec::new();
+            for (key, value) in env {
+                // Anything else would make the shell run the assignment as a command
+                let valid_name = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                    && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                if !valid_name {
+                    return Err(format!("Invalid environment variable name {key:?}"));
+                }
+                assignments.push(format!("{key}={}", quote(value)?));
+            }
+            steps.push(format!("export {}", assignments.join(" ")));
+        }
+        steps.push(command.trim().to_string());
+        Ok(steps.join(" && "))
+    }
+
+    /// The process for a collection command: through the platform shell when
+    /// `use_shell` is set, otherwise split into words by `command_args`
+    fn collection_process(command: &str, use_shell: bool) -> Result<Command, String> {
+        if use_shell {
+            if command.trim().is_empty() {
+                return Err("No command given".to_string());
+            }
+            let mut process;
+            #[cfg(windows)]
+            {
+                // cmd does its own unquoting, so pass the line through untouched
+                use std::os::windows::process::CommandExt;
+                process = Command::new("cmd");
+                process.arg("/C").raw_arg(command);
+            }
+            #[cfg(not(windows))]
+            {
+                process = Command::new("sh");
+                process.args(["-c", command]);
+            }
+            return Ok(process);
+        }
+
+        let args = Self::command_args(command)?;
+        let mut process = Command::new(&args[0]);
+        process.args(&args[1..]);
+        Ok(process)
+    }
+
+    /// Send each line of a command's output to the UI until the output closes
+    /// or the receiver is dropped
+    fn forward_lines(
+        reader: impl Read,
+        encoding: LogEncoding,
+        tx: &mpsc::Sender<CollectorMessage>,
+        repaint_ctx: Option<&egui::Context>,
+        message: fn(String) -> CollectorMessage,
+    ) {
+        let mut reader = BufReader::new(reader);
+        let mut buffer = Vec::new();
+        loop {
+            buffer.clear();
+            match reader.read_until(b'\n', &mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    if tx.send(message(encoding.decode_line(&buffer))).is_err() {
+                        break;
+                    }
+                    if let Some(ctx) = repaint_ctx {
+                        ctx.request_repaint();
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_gzip_path(path: &Path) -> bool {
+        path.extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("gz"))
+    }
+
+    /// Open a log file for line reading, transparently decompressing `.gz` files
+    fn open_log_reader(path: &Path) -> std::io::Result<Box<dyn BufRead + Send>> {
+        let file = fs::File::open(path)?;
+        if Self::is_gzip_path(path) {
+            Ok(Box::new(BufReader::new(flate2::read::MultiGzDecoder::new(
+                file,
+            ))))
+        } else {
+            Ok(Box::new(BufReader::new(file)))
+        }
+    }
+
+    /// Read a static log file into the buffer on a background thread
+    fn open_log_file(&mut self, path: PathBuf) {
+        self.open_log_files(vec![path]);
+    }
+
+    /// Files matching a glob pattern, oldest first so rotated logs read in
+    /// time order; ties fall back to the file name
+    fn expand_log_glob(pattern: &str) -> Result<Vec<PathBuf>, String> {
+        let paths = glob::glob(pattern).map_err(|err| format!("Invalid pattern: {err}"))?;
+        let mut files: Vec<(Option<std::time::SystemTime>, PathBuf)> = paths
+            .filter_map(Result::ok)
+            .filter(|path| path.is_file())
+            .map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                (modified, path)
+            })
+            .collect();
+        if files.is_empty() {
+            return Err(format!("No files match {pattern}"));
+        }
+        files.sort();
+        Ok(files.into_iter().map(|(_, path)| path).collect())
+    }
+
+    fn open_log_glob(&mut self, pattern: &str) {
+        match Self::expand_log_glob(pattern) {
+            Ok(paths) => self.open_log_files(paths),
+            Err(message) => self.notify_error(message),
+        }
+    }
+
+    /// Read log files one after another into the buffer as a single stream
+    fn open_log_files(&mut self, paths: Vec<PathBuf>) {
+        self.stop_log_collection();
+        let source = &mut self.tabs[self.active_tab];
+        source.logs.clear();
+        source.is_loading = false;
+        source.last_error = None;
+
+        // The decompressed size of a gzip file isn't known up front
+        let total = if paths.iter().any(|path| Self::is_gzip_path(path)) {
+            0
+        } else {
+            paths
+                .iter()
+                .map(|path| {
+                    fs::metadata(path)
+                        .map(|metadata| metadata.len())
+                        .unwrap_or(0)
+                })
+                .sum()
+        };
+        // Bounded so a huge file can't outrun the UI and pile up in memory
+        let (tx, rx) = mpsc::sync_channel(10_000);
+        source.log_receiver = Some(rx);
+        source.collection_state = CollectionState::Tailing;
+        source.import_progress = Some((0, total));
+        let repaint_ctx = self.egui_ctx.clone();
+        let encoding = self.settings.encoding;
+
+        let handle = thread::spawn(move || {
+            let mut line = Vec::new();
+            let mut bytes_read = 0u64;
+            let mut last_reported = 0u64;
+            for path in paths {
+                let mut reader = match Self::open_log_reader(&path) {
+                    Ok(reader) => reader,
+                    Err(err) => {
+                        let _ = tx.send(CollectorMessage::Error(format!(
+                            "Failed to open {}: {err}",
+                            path.display()
+                        )));
+                        return;
+                    }
+                };
+
+                let mut first_line = true;
+                loop {
+                    line.clear();
+                    match reader.read_until(b'\n', &mut line) {
+                        Ok(0) => break,
+                        Ok(count) => {
+                            bytes_read += count as u64;
+                            let mut content = encoding.decode_line(&line);
+                            if first_line {
+                                // Files saved by Windows tools often start with a UTF-8 BOM
+                                if let Some(stripped) = content.strip_prefix('\u{feff}') {
+                                    content = stripped.to_string();
+                                }
+                                first_line = false;
+                            }
+                            if tx.send(CollectorMessage::Line(content)).is_err() {
+                                return;
+                            }
+                            if bytes_read - last_reported >= 64 * 1024 {
+                                last_reported = bytes_read;
+                                if tx.send(CollectorMessage::Progress(bytes_read)).is_err() {
+                                    return;
+                                }
+                                if let Some(ctx) = &repaint_ctx {
+                                    ctx.request_repaint();
+                                }
+                            }
+                        }
+                        Err(err) => {
+                            let _ = tx.send(CollectorMessage::Error(format!(
+                                "Failed to read {}: {err}",
+                                path.display()
+                            )));
+                            return;
+                        }
+                    }
+                }
+            }
+            let _ = tx.send(CollectorMessage::Progress(bytes_read));
+            if let Some(ctx) = &repaint_ctx {
+                ctx.request_repaint();
+            }
+        });
+
+        source.log_thread_handle = Some(handle);
+    }
+
+    /// Turn one syslog message (RFC 5424 or RFC 3164) into a line that
+    /// `add_log_entry` understands: the timestamp first, then the severity as
+    /// a level keyword, then the rest
+    fn format_syslog_message(message: &str) -> String {
+        let message = message.trim_end_matches(['\r', '\n', '\0']);
+
+        let Some((priority, body)) = message
+            .strip_prefix('<')
+            .and_then(|rest| rest.split_once('>'))
+            .and_then(|(priority, body)| Some((priority.parse::<u8>().ok()?, body)))
+        else {
+            return message.to_string();
+        };
+        let level = match priority % 8 {
+            0 | 1 => "FATAL",
+            2 => "CRIT",
+            3 => "ERROR",
+            4 => "WARNING",
+            5 | 6 => "INFO",
+            _ => "DEBUG",
+        };
+
+        if let Some(rest) = body.strip_prefix("1 ") {
+            // RFC 5424: TIMESTAMP HOSTNAME APP-NAME PROCID MSGID STRUCTURED-DATA MSG
+            let mut parts = rest.splitn(6, ' ');
+            let timestamp = parts.next().unwrap_or("-");
+            let host = parts.next().unwrap_or("-");
+            let app = parts.next().unwrap_or("-");
+            let _proc_id = parts.next();
+            let _msg_id = parts.next();
+            let mut text = parts.next().unwrap_or("");
+            if let Some(after_nil) = text.strip_prefix('-') {
+                text = after_nil;
+            } else {
+                while text.starts_with('[') {
+                    let Some(end) = text.find(']') else { break };
+                    text = &text[end + 1..];
+                }
+            }
+            let text = text.trim_start().trim_start_matches('\u{feff}');
+            let timestamp = chrono::DateTime::parse_from_rfc3339(timestamp)
+                .map(|time| {
+                    time.with_timezone(&Local)
+                        .format(DEFAULT_TIMESTAMP_FORMAT)
+                        .to_string()
+                })
+                .unwrap_or_else(|_| Local::now().format(DEFAULT_TIMESTAMP_FORMAT).to_string());
+            format!("{timestamp} {level} {host} {app}: {text}")
+        } else {
+            // RFC 3164: "Mmm dd hh:mm:ss HOST TAG: MSG"; extraction finds the timestamp
+            match (body.get(..15), body.get(15..)) {
+                (Some(timestamp), Some(rest)) if !rest.is_empty() => {
+                    format!("{timestamp} {level} {}", rest.trim_start())
+                }
+                _ => format!("{level} {body}"),
+            }
+        }
+    }
+
+    /// Take the next complete message off the front of a TCP syslog stream.
+    /// Senders either prefix each message with its length ("LEN SP MSG",
+    /// RFC 6587 octet counting) or end it with a newline. Fails on a length
+    /// over `SYSLOG_MAX_FRAME_BYTES`, after which the stream can't be trusted.
+    fn take_syslog_frame(pending: &mut Vec<u8>) -> Result<Option<String>, String> {
+        // Messages start with `<PRI>`, so leading digits can only be a length
+        let digits = pending.iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits == pending.len() && digits < 10 {
+            return Ok(None);
+        }
+        if (1..10).contains(&digits) && pending[0] != b'0' && pending[digits] == b' ' {
+            let length: usize = String::from_utf8_lossy(&pending[..digits])
+                .parse()
+                .map_err(|err| format!("Invalid syslog frame length: {err}"))?;
+            if length > SYSLOG_MAX_FRAME_BYTES {
+                return Err(format!(
+                    "Syslog frame of {length} bytes is over the {SYSLOG_MAX_FRAME_BYTES} byte limit"
+                ));
+            }
+            let end = digits + 1 + length;
+            if pending.len() < end {
+                return Ok(None);
+            }
+            let message = String::from_utf8_lossy(&pending[digits + 1..end]).into_owned();
+            pending.drain(..end);
+            return Ok(Some(message));
+        }
+
+        let end = match pending.iter().position(|&b| b == b'\n') {
+            Some(newline) => newline + 1,
+            // A sender that never ends its lines still can't grow this forever
+            None if pending.len() >= SYSLOG_MAX_FRAME_BYTES => pending.len(),
+            None => return Ok(None),
+        };
+        let message = String::from_utf8_lossy(&pending[..end]).into_owned();
+        pending.drain(..end);
+        Ok(Some(message))
+    }
+
+    /// Listen for syslog messages on the configured port instead of running a command
+    fn start_syslog_listener(&mut self) {
+        self.stop_log_collection();
+        if self.settings.clear_on_restart {
+            self.source_mut().logs.clear();
+        }
+
+        let port = self.settings.syslog_port;
+        let protocol = self.settings.syslog_protocol;
+        let address = ("0.0.0.0", port);
+        let (tx, rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let repaint_ctx = self.egui_ctx.clone();
+
+        let handle = match protocol {
+            SyslogProtocol::Udp => {
+                let socket = match std::net::UdpSocket::bind(address) {
+                    Ok(socket) => socket,
+                    Err(err) => {
+                        self.notify_error(format!("Failed to listen on UDP port {port}: {err}"));
+                        return;
+                    }
+                };
+                let _ = socket.set_read_timeout(Some(std::time::Duration::from_millis(200)));
+                let stop = Arc::clone(&stop);
+                thread::spawn(move || {
+                    let mut buffer = vec![0u8; 64 * 1024];
+                    while !stop.load(Ordering::Relaxed) {
+                        let length = match socket.recv_from(&mut buffer) {
+                            Ok((length, _)) => length,
+                            // The read timeout lets the loop check the stop flag
+                            Err(err)
+                                if matches!(
+                                    err.kind(),
+                                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                                ) =>
+                            {
+                                continue;
+                            }
+                            Err(_) => break,
+                        };
+                        let message = String::from_utf8_lossy(&buffer[..length]);
+                        let line = Self::format_syslog_message(&message);
+                        if tx.send(CollectorMessage::Line(line)).is_err() {
+                            break;
+                        }
+                        if let Some(ctx) = &repaint_ctx {
+                            ctx.request_repaint();
+                        }
+                    }
+                })
+            }
+            SyslogProtocol::Tcp => {
+                let listener = match std::net::TcpListener::bind(address) {
+                    Ok(listener) => listener,
+                    Err(err) => {
+                        self.notify_error(format!("Failed to listen on TCP port {port}: {err}"));
+                        return;
+                    }
+                };
+                let _ = listener.set_nonblocking(true);
+                let stop = Arc::clone(&stop);
+                thread::spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        let stream = match listener.accept() {
+                            Ok((stream, _)) => stream,
+                            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                                thread::sleep(std::time::Duration::from_millis(100));
+                                continue;
+                            }
+                            Err(_) => break,
+                        };
+                        let _ = stream.set_nonblocking(false);
+                        let _ =
+                            stream.set_read_timeout(Some(std::time::Duration::from_millis(200)));
+                        let tx = tx.clone();
+                        let stop = Arc::clone(&stop);
+                        let repaint_ctx = repaint_ctx.clone();
+                        // One reader per client; a partial frame waits in `pending`
+                        thread::spawn(move || {
+                            let mut stream = stream;
+                            let mut buffer = vec![0u8; 16 * 1024];
+                            let mut pending = Vec::new();
+                            while !stop.load(Ordering::Relaxed) {
+                                let length = match stream.read(&mut buffer) {
+                                    Ok(0) => break,
+                                    Ok(length) => length,
+                                    // The read timeout lets the loop check the stop flag
+                                    Err(err)
+                                        if matches!(
+                                            err.kind(),
+                                            std::io::ErrorKind::WouldBlock
+                                                | std::io::ErrorKind::TimedOut
+                                                | std::io::ErrorKind::Interrupted
+                                        ) =>
+                                    {
+                                        continue;
+                                    }
+                                    Err(_) => break,
+                                };
+                                pending.extend_from_slice(&buffer[..length]);
+                                loop {
+                                    let message = match Self::take_syslog_frame(&mut pending) {
+                                        Ok(Some(message)) => message,
+                                        Ok(None) => break,
+                                        // Drop the client rather than buffer what it claims;
+                                        // the listener and other clients carry on
+                                        Err(_) => return,
+                                    };
+                                    if message.trim().is_empty() {
+                                        continue;
+                                    }
+                                    let line = Self::format_syslog_message(&message);
+                                    if tx.send(CollectorMessage::Line(line)).is_err() {
+                                        return;
+                                    }
+                                }
+                                if let Some(ctx) = &repaint_ctx {
+                                    ctx.request_repaint();
+                                }
+                            }
+                        });
+                    }
+                })
+            }
+        };
+
+        let source = self.source_mut();
+        source.collection_stop = stop;
+        source.log_receiver = Some(rx);
+        source.log_thread_handle = Some(handle);
+        source.collection_state = CollectionState::Tailing;
+        source.is_loading = false;
+        source.last_log_arrival = Some(Instant::now());
+        source.running_command = None;
+        source.last_error = None;
+        let protocol_name = match protocol {
+            SyslogProtocol::Udp => "UDP",
+            SyslogProtocol::Tcp => "TCP",
+        };
+        self.notify(format!(
+            "Listening for syslog on {protocol_name} port {port}"
+        ));
+    }
+
+    fn show_syslog_window(&mut self, ctx: &egui::Context) {
+        if !self.syslog_window_open {
+            return;
+        }
+
+        let mut open = true;
+        let mut start = false;
+        egui::Window::new("Syslog Listener")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.radio_value(
+                        &mut self.settings.syslog_protocol,
+                        SyslogProtocol::Udp,
+                        "UDP",
+                    );
+                    ui.radio_value(
+                        &mut self.settings.syslog_protocol,
+                        SyslogProtocol::Tcp,
+                        "TCP",
+                    );
+                    ui.label("Port:");
+                    ui.add(egui::DragValue::new(&mut self.settings.syslog_port).range(1..=65535));
+                });
+                ui.weak("Ports below 1024 usually need elevated privileges.");
+                if ui.button("Start listening").clicked() {
+                    start = true;
+                }
+            });
+
+        if start {
+            self.syslog_window_open = false;
+            self.save_settings();
+            self.start_syslog_listener();
+        } else if !open {
+            self.syslog_window_open = false;
+        }
+    }
+
+    fn stop_log_collection(&mut self) {
+        self.source_mut().stop();
+    }
+
+    /// Stop the command from the UI, keeping the buffer
+    fn stop_command(&mut self) {
+        self.stop_log_collection();
+        self.source_mut().is_loading = false;
+    }
+
+    /// Keep background tabs collecting while another one is shown
+    fn drain_background_tabs(&mut self, ctx: &egui::Context) {
+        for tab in 0..self.tabs.len() {
+            if tab != self.active_tab {
+                self.drain_collector(tab, ctx);
+            }
+        }
+    }
+
+    fn switch_tab(&mut self, index: usize) {
+        if index == self.active_tab || index >= self.tabs.len() {
+            return;
+        }
+        if self.settings.per_tab_filters {
+            let filters = self.current_filters();
+            self.source_mut().filters = Some(filters);
+        }
+        self.active_tab = index;
+        if self.settings.per_tab_filters
+            && let Some(filters) = self.source_mut().filters.take()
+        {
+            self.apply_filters(filters);
+        }
+        // Row ids belong to the other tab's buffer
+        self.selected_entry = None;
+        self.marked_entries.clear();
+        self.detail_entry = None;
+    }
+
+    /// Open a tab for another source, starting from the current command
+    fn add_tab(&mut self) {
+        let current = self.source();
+        let source = LogSource::new(
+            current.command.clone(),
+            current.follow_file,
+            current.ssh.clone(),
+        );
+        self.tabs.push(source);
+        self.switch_tab(self.tabs.len() - 1);
+    }
+
+    /// Close a tab, stopping its command
+    fn close_tab(&mut self, index: usize) {
+        if self.tabs.len() <= 1 || index >= self.tabs.len() {
+            return;
+        }
+        if index == self.active_tab {
+            self.switch_tab(if index == 0 { 1 } else { index - 1 });
+        }
+        self.save_favorite_cache(index);
+        let mut source = self.tabs.remove(index);
+        if index < self.active_tab {
+            self.active_tab -= 1;
+        }
+        source.stop();
+    }
+
+    /// Name the tab a notice came from when it isn't the one shown
+    fn tab_message(&self, tab: usize, message: impl Into<String>) -> String {
+        let message = message.into();
+        if tab == self.active_tab {
+            message
+        } else {
+            format!("{}: {message}", Self::tab_title(&self.tabs[tab].command))
+        }
+    }
+
+    /// Short label for a tab: the command, cut to fit
+    fn tab_title(command: &str) -> String {
+        let command = Self::effective_command(command);
+        if command.is_empty() {
+            return "(no command)".to_string();
+        }
+        match command.char_indices().nth(30) {
+            Some((cut, _)) => format!("{}…", &command[..cut]),
+            None => command,
+        }
+    }
+
+    fn show_tab_strip(&mut self, ctx: &egui::Context) {
+        let mut switch_to = None;
+        let mut close = None;
+        let mut add = false;
+        egui::TopBottomPanel::top("source_tabs").show_animated(
+            ctx,
+            !self.minimal_mode && self.tabs.len() > 1,
+            |ui| {
+                ui.horizontal(|ui| {
+                    for (index, source) in self.tabs.iter().enumerate() {
+                        let command = &source.command;
+                        let response = ui
+                            .selectable_label(index == self.active_tab, Self::tab_title(command))
+                            .on_hover_text(command);
+                        if response.clicked() {
+                            switch_to = Some(index);
+                        }
+                        let close_clicked =
+                            ui.small_button("✖").on_hover_text("Close tab").clicked();
+                        if close_clicked || response.middle_clicked() {
+                            close = Some(index);
+                        }
+                        ui.separator();
+                    }
+                    add = ui.button("➕").on_hover_text("New tab").clicked();
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .checkbox(&mut self.settings.per_tab_filters, "Filters per tab")
+                            .on_hover_text("Each tab keeps its own levels, search and time filter")
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                    });
+                });
+            },
+        );
+
+        if let Some(index) = switch_to {
+            self.switch_tab(index);
+        }
+        if let Some(index) = close {
+            self.close_tab(index);
+        }
+        if add {
+            self.add_tab();
+        }
+    }
+
+    /// Run the current command again after a stop or error, keeping the buffer
+    fn start_command(&mut self) {
+        self.stop_log_collection();
+        self.start_log_collection(self.active_tab);
+    }
+
+    /// Ask a command's process group to exit with SIGTERM, kill it if it's
+    /// still running after `TERMINATE_GRACE_MS`, and reap it
+    fn terminate_child(mut child: Child) {
+        #[cfg(unix)]
+        {
+            let group = -(child.id() as libc::pid_t);
+            // SAFETY: the child leads its group and hasn't been reaped yet, so
+            // the id can't have been reused
+            unsafe {
+                libc::kill(group, libc::SIGTERM);
+            }
+            let deadline = Instant::now() + std::time::Duration::from_millis(TERMINATE_GRACE_MS);
+            while Instant::now() < deadline {
+                if !matches!(child.try_wait(), Ok(None)) {
+                    return;
+                }
+                thread::sleep(std::time::Duration::from_millis(20));
+            }
+            // SAFETY: as above; the child still hasn't been reaped
+            unsafe {
+                libc::kill(group, libc::SIGKILL);
+            }
+        }
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
+    /// Add a backlog flag to a recognized follow command (`journalctl -f`,
+    /// `docker logs -f`, `kubectl logs -f`) so it starts with recent history.
+    /// Returns `None` when the command isn't recognized or already sets one.
+    fn with_backlog(command: &str, lines: usize) -> Option<String> {
+        if lines == 0 {
+            return None;
+        }
+        let mut args = shlex::split(command)?;
+        if !Self::follows(&args) {
+            return None;
+        }
+
+        let program = Path::new(args.first()?).file_name()?.to_str()?;
+        let (flag, insert_at) = match program {
+            "journalctl" => {
+                let has_backlog = args
+                    .iter()
+                    .any(|arg| arg.starts_with("-n") || arg.starts_with("--lines") || arg == "-e");
+                if has_backlog {
+                    return None;
+                }
+                ("-n", 1)
+            }
+            "docker" | "podman" | "kubectl" => {
+                if args.iter().any(|arg| arg.starts_with("--tail")) {
+                    return None;
+                }
+                ("--tail", args.iter().position(|arg| arg == "logs")? + 1)
+            }
+            _ => return None,
+        };
+        args.splice(insert_at..insert_at, [flag.to_string(), lines.to_string()]);
+        shlex::try_join(args.iter().map(String::as_str)).ok()
+    }
+
+    /// Whether the split command keeps streaming (`-f` or `--follow`)
+    fn follows(args: &[String]) -> bool {
+        args.iter().any(|arg| arg == "-f" || arg == "--follow")
+    }
+
+    /// Whether the command streams from a remote host or container, where
+    /// connections can drop independently of the log source
+    fn is_remote_command(command: &str) -> bool {
+        let effective = Self::effective_command(command);
+        let program = effective.split_whitespace().next().unwrap_or("");
+        matches!(program, "ssh" | "docker" | "podman" | "kubectl")
+    }
+
+    /// Schedule a restart after the command exited on its own, when enabled,
+    /// and mark the gap in the buffer. The delay doubles after each quick
+    /// exit, up to `MAX_RESTART_BACKOFF_SECS`.
+    fn handle_command_exit(&mut self, tab: usize, code: Option<i32>) {
+        if !self.settings.auto_restart {
+            return;
+        }
+        let code = code.map_or("signal".to_string(), |code| format!("code {code}"));
+        let source = &mut self.tabs[tab];
+        let ran_long = source
+            .spawned_at
+            .is_some_and(|spawned| spawned.elapsed().as_secs() >= MAX_RESTART_BACKOFF_SECS);
+        let delay = if source.restart_backoff == 0 || ran_long {
+            self.settings.restart_backoff_secs
+        } else {
+            source.restart_backoff
+        }
+        .max(1);
+        source.restart_backoff = (delay * 2).min(MAX_RESTART_BACKOFF_SECS);
+        self.push_marker(
+            tab,
+            format!("--- command exited ({code}), restarting in {delay}s ---"),
+        );
+        self.tabs[tab].restart_at = Some(Instant::now() + std::time::Duration::from_secs(delay));
+        if let Some(ctx) = &self.egui_ctx {
+            ctx.request_repaint_after(std::time::Duration::from_secs(delay));
+        }
+    }
+
+    /// Run the command of tab `tab` again after it exited on its own. Unlike a
+    /// restart from the UI this keeps the buffer, and the exit badge stays up
+    /// until the new process reports in.
+    fn restart_after_exit(&mut self, tab: usize) {
+        let source = &mut self.tabs[tab];
+        let status = source.process_status;
+        source.stop();
+        self.start_log_collection(tab);
+        let source = &mut self.tabs[tab];
+        source.process_status = status;
+        // The exit marker stays in view instead of the loading spinner
+        source.is_loading = source.logs.is_empty();
+    }
+
+    /// Restart the command of tab `tab` while keeping the buffer, marking the gap
+    fn reconnect_log_collection(&mut self, tab: usize) {
+        self.tabs[tab].stop();
+        self.push_marker(tab, "--- reconnected ---".to_string());
+        self.start_log_collection(tab);
+    }
+
+    /// Wipe the buffer but keep it around for a single-level undo
+    fn clear_logs(&mut self) {
+        let source = self.source_mut();
+        // Clearing nothing mustn't throw away the lines an earlier clear kept
+        if source.logs.is_empty() {
+            return;
+        }
+        let count = source.logs.len();
+        source.cleared_logs = Some(std::mem::take(&mut source.logs));
+        self.notify(format!(
+            "Cleared {count} lines — File › Undo Clear restores them"
+        ));
+    }
+
+    /// Put the cleared lines back in front of anything that arrived since,
+    /// dropping the oldest past `max_log_entries`
+    fn undo_clear_logs(&mut self) {
+        let max_entries = self.settings.max_log_entries;
+        let source = self.source_mut();
+        if let Some(mut restored) = source.cleared_logs.take() {
+            let count = restored.len();
+            restored.append(&mut source.logs);
+            let overflow = restored.len().saturating_sub(max_entries);
+            restored.drain(..overflow);
+            source.trimmed_lines += overflow as u64;
+            source.logs = restored;
+            self.notify(format!("Restored {} lines", count.saturating_sub(overflow)));
+        }
+    }
+
+    fn restart_log_collection(&mut self) {
+        self.stop_log_collection();
+        let clear = self.settings.clear_on_restart;
+        let source = self.source_mut();
+        source.cleared_logs = None;
+        if clear {
+            source.logs.clear();
+        }
+        source.is_loading = false;
+        self.start_log_collection(self.active_tab);
+    }
+
+    /// Add a `--- … ---` line of our own to tab `tab`. It skips everything
+    /// `add_log_entry` does for arrivals, so it is never sampled away and
+    /// doesn't reset the idle timer or count towards bursts and cues.
+    fn push_marker(&mut self, tab: usize, text: String) {
+        let id = self.next_entry_id;
+        self.next_entry_id += 1;
+        self.tabs[tab].logs.push(LogEntry {
+            id,
+            timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            content: text.clone(),
+            raw: text,
+            fields: Vec::new(),
+            received_at: Instant::now(),
+            level: None,
+            is_stderr: false,
+        });
+    }
+
+    /// Add a line to the buffer of the source in tab `tab`
+    fn add_log_entry(&mut self, tab: usize, content: String, is_stderr: bool) {
+        let content = Self::truncate_line(
+            Self::normalize_line_endings(content),
+            self.settings.max_line_bytes,
+        );
+        let source = &mut self.tabs[tab];
+        source.last_log_arrival = Some(Instant::now());
+
+        // Set loading to false when we receive the first log entry
+        if source.is_loading {
+            source.is_loading = false;
+            source.cached_preview.clear();
+        }
+
+        // Raw mode keeps the line verbatim and stamps it with the arrival time
+        let (extracted_timestamp, cleaned_content) = if self.settings.raw_mode {
+            (None, content.clone())
+        } else if self.settings.first_token_timestamp
+            && let Some(split) =
+                Self::first_token_timestamp(&content, &self.settings.first_token_format)
+        {
+            split
+        } else {
+            Self::extract_timestamp_from_log(&content)
+        };
+
+        // Checked after extraction so a line holding only a timestamp counts as blank
+        if self.settings.skip_blank_lines && cleaned_content.trim().is_empty() {
+            return;
+        }
+
+        let timestamp = extracted_timestamp
+            .unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string());
+
+        let level = self.entry_level(&cleaned_content);
+        if self.settings.sample_every > 1 {
+            let always_keep = level.is_some_and(|level| {
+                self.settings
+                    .sample_keep_levels
+                    .iter()
+                    .any(|keep| keep == level)
+            });
+            let source = &mut self.tabs[tab];
+            source.sampled_lines += 1;
+            if !always_keep && source.sampled_lines % u64::from(self.settings.sample_every) != 1 {
+                return;
+            }
+        }
+
+        let mut fields = if self.settings.raw_mode {
+            Vec::new()
+        } else {
+            Self::parse_fields(&cleaned_content)
+        };
+        let extraction_patterns: &[Regex] = if self.settings.raw_mode {
+            &[]
+        } else {
+            &self.extraction_patterns
+        };
+        for pattern in extraction_patterns {
+            if let Some(captures) = pattern.captures(&cleaned_content) {
+                for name in pattern.capture_names().flatten() {
+                    if let Some(value) = captures.name(name) {
+                        fields.push((name.to_string(), value.as_str().to_string()));
+                    }
+                }
+            }
+        }
+
+        if level.is_some_and(Self::is_error_level) {
+            self.record_error_arrival(tab);
+        }
+        let id = self.next_entry_id;
+        self.next_entry_id += 1;
+
+        let source = &mut self.tabs[tab];
+        if let Some(group) = level.and_then(Self::cue_group) {
+            source.new_cue_groups[group] = true;
+        }
+        source.logs.push(LogEntry {
+            id,
+            timestamp,
+            content: cleaned_content,
+            raw: content,
+            fields,
+            received_at: Instant::now(),
+            level,
+            is_stderr,
+        });
+
+        let max_entries = self.settings.max_log_entries;
+        if source.logs.len() > max_entries {
+            // Trim in batches so we don't shift the buffer on every line
+            let overflow = source.logs.len() - max_entries;
+            let dropped = overflow.max(max_entries / 10);
+            source.logs.drain(0..dropped);
+            let first_trim = source.trimmed_lines == 0;
+            source.trimmed_lines += dropped as u64;
+            if first_trim {
+                self.notify(self.tab_message(tab, "Buffer full, dropping the oldest lines"));
+            }
+        }
+    }
+
+    /// Index into `CUE_LEVELS` for a detected level
+    fn cue_group(level: &str) -> Option<usize> {
+        match level {
+            "warn" | "warning" => Some(0),
+            "error" | "err" => Some(1),
+            "fatal" | "critical" | "crit" => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Fire the cue of the most severe level that arrived this frame and has
+    /// one configured, at most once per `CUE_DEBOUNCE_SECS`
+    fn play_level_cues(&mut self, arrived: [bool; 3]) {
+        if self.settings.cues_muted
+            || self
+                .last_cue
+                .is_some_and(|last| last.elapsed().as_secs() < CUE_DEBOUNCE_SECS)
+        {
+            return;
+        }
+
+        let cue = CUE_LEVELS
+            .iter()
+            .zip(arrived)
+            .rev()
+            .filter(|(_, arrived)| *arrived)
+            .find_map(|((level, _), _)| {
+                self.settings
+                    .level_cues
+                    .get(*level)
+                    .filter(|cue| cue.sound || cue.flash)
+                    .map(|cue| (*cue, Self::level_color(level)))
+            });
+        let Some((cue, color)) = cue else {
+            return;
+        };
+
+        self.last_cue = Some(Instant::now());
+        if cue.flash {
+            self.cue_flash = Some((Instant::now(), color));
+        }
+        if cue.sound {
+            Self::beep();
+        }
+    }
+
+    /// A short, quiet tone, played by one audio thread that keeps the output
+    /// stream open for every later cue
+    #[cfg(feature = "sound")]
+    fn beep() {
+        static TONES: LazyLock<mpsc::Sender<()>> = LazyLock::new(|| {
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || {
+                use rodio::Source;
+                let Ok((_stream, handle)) = rodio::OutputStream::try_default() else {
+                    return;
+                };
+                for () in rx {
+                    let Ok(sink) = rodio::Sink::try_new(&handle) else {
+                        continue;
+                    };
+                    sink.append(
+                        rodio::source::SineWave::new(880.0)
+                            .take_duration(std::time::Duration::from_millis(150))
+                            .amplify(0.2),
+                    );
+                    sink.detach();
+                }
+            });
+            tx
+        });
+        // Fails only when there is no audio device, and then there is nothing to play on
+        let _ = TONES.send(());
+    }
+
+    /// The terminal bell, for builds without the `sound` feature
+    #[cfg(not(feature = "sound"))]
+    fn beep() {
+        use std::io::Write;
+        let mut stderr = std::io::stderr();
+        let _ = stderr.write_all(b"\x07").and_then(|()| stderr.flush());
+    }
+
+    /// Fading border around the window after a flash cue
+    fn draw_cue_flash(&mut self, ctx: &egui::Context) {
+        let Some((started, color)) = self.cue_flash else {
+            return;
+        };
+        let elapsed = started.elapsed().as_secs_f32();
+        if elapsed >= CUE_FLASH_SECS {
+            self.cue_flash = None;
+            return;
+        }
+
+        let alpha = 1.0 - elapsed / CUE_FLASH_SECS;
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("cue_flash"),
+        ));
+        painter.rect_stroke(
+            ctx.screen_rect().shrink(3.0),
+            0.0,
+            egui::Stroke::new(6.0, color.gamma_multiply(alpha)),
+        );
+        ctx.request_repaint();
+    }
+
+    fn is_error_level(level: &str) -> bool {
+        matches!(level, "error" | "err" | "fatal" | "critical" | "crit")
+    }
+
+    /// Count an error line in the sliding burst window of tab `tab` and raise
+    /// the alert when the threshold is crossed
+    fn record_error_arrival(&mut self, tab: usize) {
+        if self.settings.burst_threshold == 0 {
+            return;
+        }
+        let window = std::time::Duration::from_secs(self.settings.burst_window_secs);
+        let source = &mut self.tabs[tab];
+        let now = Instant::now();
+        source.error_arrivals.push_back(now);
+        source.prune_error_arrivals(now, window);
+        let bursting = source.error_arrivals.len() > self.settings.burst_threshold;
+        let started = bursting && !source.in_error_burst;
+        source.in_error_burst = bursting;
+        if started && self.settings.burst_notify {
+            let message = format!(
+                "Error burst: more than {} errors in {}s",
+                self.settings.burst_threshold, self.settings.burst_window_secs
+            );
+            self.notify_error(self.tab_message(tab, message));
+        }
+    }
+
+    /// Let the alert of every tab lapse once the window has slid past the burst
+    fn update_error_burst(&mut self) {
+        let window = std::time::Duration::from_secs(self.settings.burst_window_secs);
+        let threshold = self.settings.burst_threshold;
+        let now = Instant::now();
+        for source in self.tabs.iter_mut().filter(|source| source.in_error_burst) {
+            source.prune_error_arrivals(now, window);
+            source.in_error_burst = threshold > 0 && source.error_arrivals.len() > threshold;
+        }
+    }
+
+    /// Drop trailing carriage returns and turn any remaining `\r\n` or lone
+    /// `\r` inside the line into `\n`
+    fn normalize_line_endings(content: String) -> String {
+        let trimmed = content.trim_end_matches(['\r', '\n']);
+        if !trimmed.contains('\r') {
+            return if trimmed.len() == content.len() {
+                content
+            } else {
+                trimmed.to_string()
+            };
+        }
+        trimmed.replace("\r\n", "\n").replace('\r', "\n")
+    }
+
+    /// Keep the first `max_bytes` of a pathological line so it can't stall text layout
+    fn truncate_line(mut content: String, max_bytes: usize) -> String {
+        if max_bytes == 0 || content.len() <= max_bytes {
+            return content;
+        }
+        let mut cut = max_bytes;
+        while !content.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        content.truncate(cut);
+        content.push_str("…[truncated]");
+        content
+    }
+
+    /// Level from the user's level pattern when one is configured, otherwise
+    /// from keyword detection
+    fn entry_level(&self, content: &str) -> Option<&'static str> {
+        let Some(regex) = &self.level_regex else {
+            return Self::detect_level(content);
+        };
+        let captured = regex
+            .captures(content)?
+            .name("level")?
+            .as_str()
+            .to_lowercase();
+        LEVEL_KEYWORDS
+            .iter()
+            .copied()
+            .find(|&keyword| keyword == captured)
+    }
+
+    /// The level keyword appearing earliest in the line, using the same
+    /// substring matching as the level filter. The longer spelling wins when
+    /// one keyword is a prefix of another ("warning" over "warn").
+    fn detect_level(content: &str) -> Option<&'static str> {
+        let content_lower = content.to_lowercase();
+        LEVEL_KEYWORDS
+            .iter()
+            .filter_map(|&level| content_lower.find(level).map(|position| (position, level)))
+            .min_by_key(|&(position, level)| (position, std::cmp::Reverse(level.len())))
+            .map(|(_, level)| level)
+    }
+
+    fn level_color(level: &str) -> egui::Color32 {
+        match level {
+            "trace" => egui::Color32::from_rgb(140, 140, 140),
+            "debug" => egui::Color32::from_rgb(100, 150, 230),
+            "info" => egui::Color32::from_rgb(80, 200, 120),
+            "warn" | "warning" => egui::Color32::from_rgb(230, 190, 60),
+            "error" | "err" => egui::Color32::from_rgb(230, 80, 80),
+            _ => egui::Color32::from_rgb(200, 80, 200),
+        }
+    }
+
+    /// Whether severity chips are colored; defaults to off when `NO_COLOR` is set
+    fn colorize_levels(&self) -> bool {
+        self.settings
+            .colorize_levels
+            .unwrap_or_else(|| std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()))
+    }
+
+    /// Shape cue for a level so severity doesn't depend on color alone
+    fn level_marker(level: &str) -> &'static str {
+        match level {
+            "fatal" | "critical" | "crit" => "!!! ",
+            "error" | "err" => "!! ",
+            "warn" | "warning" => "! ",
+            _ => "",
+        }
+    }
+
+    /// Compact "time ago" label, e.g. "45s ago" or "1h2m ago"
+    fn format_relative(elapsed: Duration) -> String {
+        let seconds = elapsed.num_seconds();
+        if seconds < 1 {
+            return "just now".to_string();
+        }
+        let (days, hours, minutes) = (seconds / 86_400, seconds / 3600 % 24, seconds / 60 % 60);
+        if days > 0 {
+            format!("{days}d{hours}h ago")
+        } else if hours > 0 {
+            format!("{hours}h{minutes}m ago")
+        } else if minutes > 0 {
+            format!("{minutes}m ago")
+        } else {
+            format!("{seconds}s ago")
+        }
+    }
+
+    fn is_valid_time_format(format: &str) -> bool {
+        !format.is_empty()
+            && chrono::format::StrftimeItems::new(format)
+                .all(|item| !matches!(item, chrono::format::Item::Error))
+    }
+
+    /// The configured display format, or the default when it doesn't parse
+    fn timestamp_display_format(&self) -> &str {
+        let format = self.settings.timestamp_display_format.as_str();
+        if Self::is_valid_time_format(format) {
+            format
+        } else {
+            DEFAULT_TIMESTAMP_FORMAT
+        }
+    }
+
+    /// Text for the timestamp column, falling back to the raw timestamp when
+    /// the time can't be parsed
+    fn display_timestamp(&self, timestamp: &str, now: NaiveDateTime, format: &str) -> String {
+        match Self::parse_time_input(timestamp) {
+            Some(time) if self.settings.relative_timestamps => Self::format_relative(now - time),
+            Some(time) if format != DEFAULT_TIMESTAMP_FORMAT => time.format(format).to_string(),
+            _ => timestamp.to_string(),
+        }
+    }
+
+    /// Byte ranges of the URLs in `text`. Trailing punctuation is left out of
+    /// a URL so "see https://example.com." links correctly.
+    fn url_ranges(text: &str) -> Vec<Range<usize>> {
+        URL_PATTERN
+            .find_iter(text)
+            .filter_map(|found| {
+                let url = found
+                    .as_str()
+                    .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '}']);
+                (url.len() > "https://".len()).then(|| found.start()..found.start() + url.len())
+            })
+            .collect()
+    }
+
+    /// A case-insensitive matcher and color for each whitespace-separated
+    /// search term. Field queries and the AND/OR keywords aren't highlighted.
+    fn search_term_highlighters(&self) -> Vec<(Regex, egui::Color32)> {
+        if self.search_text.trim().is_empty() || FIELD_QUERY_PATTERN.is_match(&self.search_text) {
+            return Vec::new();
+        }
+        let mut terms: Vec<&str> = Vec::new();
+        for term in self.search_text.split_whitespace() {
+            if !matches!(term, "AND" | "OR") && !terms.contains(&term) {
+                terms.push(term);
+            }
+        }
+        terms
+            .iter()
+            .zip(TERM_HIGHLIGHT_COLORS.iter().cycle())
+            .filter_map(|(term, &color)| {
+                // Keep `^`/`$` anchors so only the anchored occurrence lights up
+                let (start, term) = match term.strip_prefix('^') {
+                    Some(rest) => ("^", rest),
+                    None => ("", *term),
+                };
+                let (end, term) = match term.strip_suffix('$') {
+                    Some(rest) => ("$", rest),
+                    None => ("", term),
+                };
+                if term.is_empty() {
+                    return None;
+                }
+                regex::RegexBuilder::new(&format!("{start}{}{end}", regex::escape(term)))
+                    .case_insensitive(true)
+                    .build()
+                    .ok()
+                    .map(|regex| (regex, color))
+            })
+            .collect()
+    }
+
+    /// Lay out text with each search term match on its own color and `links`
+    /// underlined. Where matches overlap, the earlier one wins.
+    fn highlight_terms(
+        text: &str,
+        highlighters: &[(Regex, egui::Color32)],
+        links: &[Range<usize>],
+        selection: Option<egui::Color32>,
+        text_color: Option<egui::Color32>,
+        style: &egui::Style,
+    ) -> egui::text::LayoutJob {
+        let mut matches: Vec<(usize, usize, egui::Color32)> = highlighters
+            .iter()
+            .flat_map(|(regex, color)| {
+                regex
+                    .find_iter(text)
+                    .map(move |found| (found.start(), found.end(), *color))
+            })
+            .collect();
+        matches.sort_by_key(|&(start, end, _)| (start, std::cmp::Reverse(end)));
+        let mut highlights = Vec::new();
+        let mut position = 0;
+        for (start, end, color) in matches {
+            if start >= position {
+                highlights.push((start..end, color));
+                position = end;
+            }
+        }
+
+        let plain = egui::TextFormat {
+            font_id: egui::TextStyle::Body.resolve(style),
+            color: text_color.unwrap_or_else(|| style.visuals.text_color()),
+            background: selection.unwrap_or(egui::Color32::TRANSPARENT),
+            ..Default::default()
+        };
+        let mut cuts: Vec<usize> = highlights
+            .iter()
+            .map(|(range, _)| range)
+            .chain(links)
+            .flat_map(|range| [range.start, range.end])
+            .chain([0, text.len()])
+            .collect();
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        let mut job = egui::text::LayoutJob::default();
+        for cut in cuts.windows(2) {
+            let (start, end) = (cut[0], cut[1]);
+            let mut format = plain.clone();
+            if links
+                .iter()
+                .any(|link| link.start <= start && end <= link.end)
+            {
+                format.color = style.visuals.hyperlink_color;
+                format.underline = egui::Stroke::new(1.0, style.visuals.hyperlink_color);
+            }
+            if let Some((_, color)) = highlights
+                .iter()
+                .find(|(range, _)| range.start <= start && end <= range.end)
+            {
+                format.color = egui::Color32::BLACK;
+                format.background = *color;
+            }
+            job.append(&text[start..end], 0.0, format);
+        }
+        job
+    }
+
+    fn level_chip(level: &str, colorize: bool, show_marker: bool) -> egui::RichText {
+        let marker = if show_marker {
+            Self::level_marker(level)
+        } else {
+            ""
+        };
+        let text = egui::RichText::new(format!("{marker}{}", level.to_uppercase()))
+            .small()
+            .strong();
+        if colorize {
+            text.color(egui::Color32::BLACK)
+                .background_color(Self::level_color(level))
+        } else {
+            text
+        }
+    }
+
+    /// Right-click menu shared by the cells of a log row. Copying happens
+    /// in place; actions that change app state are returned to the caller.
+    fn row_context_menu(
+        &self,
+        ui: &mut egui::Ui,
+        entry: &LogEntry,
+        selection_len: usize,
+    ) -> Option<RowAction> {
+        let is_pinned = self
+            .pinned_entries
+            .iter()
+            .any(|pinned| pinned.id == entry.id);
+        let mut action = None;
+        if ui.button("📋 Copy line").clicked() {
+            ui.output_mut(|o| o.copied_text = format!("{} {}", entry.timestamp, entry.content));
+            ui.close_menu();
+        }
+        let pin_label = if is_pinned {
+            "Unpin line"
+        } else {
+            "📌 Pin line"
+        };
+        if ui.button(pin_label).clicked() {
+            action = Some(RowAction::TogglePin);
+            ui.close_menu();
+        }
+        if ui.button("🔍 Show details").clicked() {
+            action = Some(RowAction::OpenDetails);
+            ui.close_menu();
+        }
+        if !self.settings.row_action_command.trim().is_empty()
+            && ui
+                .button("▶ Run row action")
+                .on_hover_text(&self.settings.row_action_command)
+                .clicked()
+        {
+            action = Some(RowAction::RunCommand);
+            ui.close_menu();
+        }
+        ui.separator();
+        if ui
+            .add_enabled(
+                selection_len > 0,
+                egui::Button::new(format!("🗑 Delete selected ({selection_len})")),
+            )
+            .on_hover_text(
+                "Removes the lines from the buffer; re-run the command to fetch them again",
+            )
+            .clicked()
+        {
+            action = Some(RowAction::DeleteSelected);
+            ui.close_menu();
+        }
+        action
+    }
+
+    /// Thin strip beside the log view with a tick for every warning or error,
+    /// positioned by its index in the filtered list. Returns the entry whose
+    /// tick is closest to a click.
+    fn show_severity_minimap(ui: &mut egui::Ui, logs: &[&LogEntry]) -> Option<u64> {
+        egui::SidePanel::right("severity_minimap")
+            .exact_width(12.0)
+            .resizable(false)
+            .frame(egui::Frame::none())
+            .show_inside(ui, |ui| {
+                let rect = ui.available_rect_before_wrap();
+                let response = ui
+                    .allocate_rect(rect, egui::Sense::click())
+                    .on_hover_text("Warnings and errors in the filtered view; click to jump");
+                let painter = ui.painter();
+                painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+                let ticks: Vec<(usize, &LogEntry)> = logs
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, entry)| {
+                        entry.level.is_some_and(|level| {
+                            matches!(
+                                level,
+                                "warn"
+                                    | "warning"
+                                    | "error"
+                                    | "err"
+                                    | "fatal"
+                                    | "critical"
+                                    | "crit"
+                            )
+                        })
+                    })
+                    .map(|(index, entry)| (index, *entry))
+                    .collect();
+                let scale = rect.height() / logs.len().max(1) as f32;
+
+                // Many lines share a pixel row in long buffers; draw each row once
+                let mut last_row = None;
+                for (index, entry) in &ticks {
+                    let y = rect.top() + (*index as f32 + 0.5) * scale;
+                    let row = (y.round() as i32, entry.level);
+                    if last_row == Some(row) {
+                        continue;
+                    }
+                    last_row = Some(row);
+                    let color = entry.level.map(Self::level_color).unwrap_or_default();
+                    painter.hline(rect.x_range(), y, egui::Stroke::new(2.0, color));
+                }
+
+                let position = response
+                    .interact_pointer_pos()
+                    .filter(|_| response.clicked())?;
+                let target = ((position.y - rect.top()) / scale) as usize;
+                ticks
+                    .iter()
+                    .min_by_key(|(index, _)| index.abs_diff(target))
+                    .map(|(_, entry)| entry.id)
+            })
+            .inner
+    }
+
+    /// Run the configured row action for an entry on a background thread.
+    /// The template is split into arguments before substitution, so values
+    /// containing spaces or quotes stay a single argument without a shell.
+    fn run_row_action(&mut self, id: u64) {
+        let Some(entry) = self.source().logs.iter().find(|entry| entry.id == id) else {
+            return;
+        };
+        let mut values: HashMap<String, String> = entry.fields.iter().cloned().collect();
+        values.insert("content".to_string(), entry.content.clone());
+        values.insert("timestamp".to_string(), entry.timestamp.clone());
+        values.insert("raw".to_string(), entry.raw.clone());
+        values.insert(
+            "level".to_string(),
+            entry.level.unwrap_or_default().to_string(),
+        );
+
+        let mut args = self
+            .settings
+            .row_action_command
+            .split_whitespace()
+            .map(|token| Self::substitute_placeholders(token, &values));
+        let Some(program) = args.next() else {
+            return;
+        };
+        let args: Vec<String> = args.collect();
+        Self::spawn_for_output(
+            program,
+            args,
+            self.row_action_tx.clone(),
+            self.egui_ctx.clone(),
+        );
+    }
+
+    /// Run a program to completion on a thread, sending back its trimmed
+    /// stdout, or an error carrying its stderr
+    fn spawn_for_output(
+        program: String,
+        args: Vec<String>,
+        tx: mpsc::Sender<Result<String, String>>,
+        repaint_ctx: Option<egui::Context>,
+    ) {
+        thread::spawn(move || {
+            let result = match Command::new(&program)
+                .args(&args)
+                .stdin(Stdio::null())
+                .output()
+            {
+                Ok(output) if output.status.success() => {
+                    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+                }
+                Ok(output) => Err(format!(
+                    "`{program}` exited with {}: {}",
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )),
+                Err(err) => Err(format!("Failed to start `{program}`: {err}")),
+            };
+            let _ = tx.send(result);
+            if let Some(ctx) = &repaint_ctx {
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    fn poll_row_action_results(&mut self) {
+        while let Ok(result) = self.row_action_rx.try_recv() {
+            match result {
+                Ok(output) if self.settings.show_row_action_output && !output.is_empty() => {
+                    // Long output would swamp the toast area
+                    let output: String = output.chars().take(500).collect();
+                    self.notify(output);
+                }
+                Ok(_) => {}
+                Err(message) => self.notify_error(message),
+            }
+        }
+    }
+
+    fn toggle_pin(&mut self, id: u64) {
+        if let Some(position) = self.pinned_entries.iter().position(|entry| entry.id == id) {
+            self.pinned_entries.remove(position);
+        } else if let Some(entry) = self.tabs[self.active_tab]
+            .logs
+            .iter()
+            .find(|entry| entry.id == id)
+        {
+            self.pinned_entries.push(entry.clone());
+        }
+    }
+
+    /// Drop entries older than the configured maximum age
+    fn evict_expired_entries(&mut self) {
+        let Some(max_age) = self.settings.max_entry_age_secs else {
+            return;
+        };
+        let max_age = std::time::Duration::from_secs(max_age);
+        // Entries are stored in arrival order, so the expired ones form a prefix
+        for source in &mut self.tabs {
+            let expired = source
+                .logs
+                .partition_point(|entry| entry.received_at.elapsed() > max_age);
+            if expired > 0 {
+                source.logs.drain(0..expired);
+            }
+        }
+    }
+
+    /// `timestamp content` lines for the clipboard, keeping only the newest
+    /// lines that fit in `max_bytes`. Returns the text and how many lines it holds.
+    fn logs_as_text(entries: &[&LogEntry], max_bytes: usize) -> (String, usize) {
+        let mut lines = Vec::new();
+        let mut size = 0;
+        for entry in entries.iter().rev() {
+            let line = format!("{} {}", entry.timestamp, entry.content);
+            size += line.len() + 1;
+            if size > max_bytes {
+                break;
+            }
+            lines.push(line);
+        }
+        lines.reverse();
+        let count = lines.len();
+        (lines.join("\n"), count)
+    }
+
+    /// Format entries as a Markdown table for pasting into tickets or chat
+    fn logs_as_markdown(entries: &[&LogEntry]) -> String {
+        let escape = |text: &str| text.replace('|', "\\|").replace('\n', "<br>");
+        let mut markdown = String::from("| Timestamp | Content |\n| --- | --- |\n");
+        for entry in entries {
+            markdown.push_str(&format!(
+                "| {} | {} |\n",
+                escape(&entry.timestamp),
+                escape(&entry.content)
+            ));
+        }
+        markdown
+    }
+
+    /// Parse a combo like `Ctrl+Shift+K` or `F11`; `None` for unknown keys or
+    /// more than one non-modifier key
+    fn parse_shortcut(text: &str) -> Option<egui::KeyboardShortcut> {
+        let mut modifiers = egui::Modifiers::NONE;
+        let mut key = None;
+        for part in text.split('+').map(str::trim) {
+            match part.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers = modifiers | egui::Modifiers::CTRL,
+                "shift" => modifiers = modifiers | egui::Modifiers::SHIFT,
+                "alt" | "option" => modifiers = modifiers | egui::Modifiers::ALT,
+                "cmd" | "command" => modifiers = modifiers | egui::Modifiers::COMMAND,
+                _ if key.is_none() => key = Some(egui::Key::from_name(part)?),
+                _ => return None,
+            }
+        }
+        Some(egui::KeyboardShortcut::new(modifiers, key?))
+    }
+
+    /// The user's binding for an action, or its default when unset or invalid
+    fn shortcut(&self, action: &str) -> Option<egui::KeyboardShortcut> {
+        self.settings
+            .keybindings
+            .get(action)
+            .and_then(|combo| Self::parse_shortcut(combo))
+            .or_else(|| {
+                let (_, _, default) = KEY_ACTIONS.iter().find(|(name, _, _)| *name == action)?;
+                Self::parse_shortcut(default)
+            })
+    }
+
+    /// Move the row cursor with j/k (or their rebinding) or the arrow keys and
+    /// open details with Enter
+    fn handle_row_navigation(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        let shortcuts = [
+            self.shortcut("next_row"),
+            self.shortcut("previous_row"),
+            self.shortcut("open_details"),
+            self.shortcut("close_details"),
+        ];
+        let (down, up, open, close) = ctx.input_mut(|i| {
+            let [down, up, open, close] = shortcuts
+                .map(|shortcut| shortcut.is_some_and(|shortcut| i.consume_shortcut(&shortcut)));
+            (
+                down || i.key_pressed(egui::Key::ArrowDown),
+                up || i.key_pressed(egui::Key::ArrowUp),
+                open,
+                close,
+            )
+        });
+
+        if close {
+            self.detail_entry = None;
+        }
+        if open && self.selected_entry.is_some() {
+            self.detail_entry = self.selected_entry;
+        }
+
+        if down || up {
+            let ids: Vec<u64> = self.filtered_logs().iter().map(|entry| entry.id).collect();
+            if ids.is_empty() {
+                return;
+            }
+
+            let position = self
+                .selected_entry
+                .and_then(|selected| ids.iter().position(|&id| id == selected));
+            let next = match position {
+                Some(position) if down => (position + 1).min(ids.len() - 1),
+                Some(position) => position.saturating_sub(1),
+                // Start from the newest line, where a tailing user is looking
+                None => ids.len() - 1,
+            };
+            self.selected_entry = Some(ids[next]);
+            self.scroll_to_selected = true;
+            self.auto_scroll = false;
+        }
+    }
+
+    /// Update the selection for a click: Ctrl toggles a row, Shift extends
+    /// from the cursor row across the visible lines, a plain click selects one
+    fn click_entry(&mut self, id: u64, modifiers: egui::Modifiers) {
+        if modifiers.shift
+            && let Some(anchor) = self.selected_entry
+        {
+            let ids: Vec<u64> = self.filtered_logs().iter().map(|entry| entry.id).collect();
+            if let (Some(from), Some(to)) = (
+                ids.iter().position(|&entry| entry == anchor),
+                ids.iter().position(|&entry| entry == id),
+            ) {
+                self.marked_entries
+                    .extend(&ids[from.min(to)..=from.max(to)]);
+            }
+        } else if modifiers.command {
+            if let Some(previous) = self.selected_entry {
+                self.marked_entries.insert(previous);
+            }
+            if !self.marked_entries.remove(&id) {
+                self.marked_entries.insert(id);
+            }
+        } else {
+            self.marked_entries.clear();
+        }
+        self.selected_entry = Some(id);
+    }
+
+    fn selected_ids(&self) -> HashSet<u64> {
+        let mut ids = self.marked_entries.clone();
+        ids.extend(self.selected_entry);
+        ids
+    }
+
+    /// Remove the selected rows from the buffer for good
+    fn delete_selected_entries(&mut self) {
+        let ids = self.selected_ids();
+        let logs = &mut self.source_mut().logs;
+        let before = logs.len();
+        logs.retain(|entry| !ids.contains(&entry.id));
+        let removed = before - logs.len();
+        self.marked_entries.clear();
+        self.selected_entry = None;
+        self.notify(format!(
+            "Deleted {removed} lines from the buffer; re-run the command to fetch them again"
+        ));
+    }
+
+    /// Step a predefined span up or down the list, or double or halve a
+    /// relative amount. Custom ranges are left alone.
+    fn zoom_time_window(&mut self, wider: bool) {
+        let filter = &mut self.settings.time_filter;
+        match &filter.mode {
+            TimeSpanMode::Predefined(span) => {
+                let position = PredefinedSpan::ALL
+                    .iter()
+                    .position(|candidate| candidate == span)
+                    .unwrap_or(0);
+                let next = if wider {
+                    (position + 1).min(PredefinedSpan::ALL.len() - 1)
+                } else {
+                    position.saturating_sub(1)
+                };
+                filter.mode = TimeSpanMode::Predefined(PredefinedSpan::ALL[next].clone());
+            }
+            TimeSpanMode::Relative => {
+                filter.relative_amount = if wider {
+                    filter.relative_amount.saturating_mul(2)
+                } else {
+                    (filter.relative_amount / 2).max(1)
+                };
+            }
+            TimeSpanMode::Disabled | TimeSpanMode::Custom => {}
+        }
+    }
+
+    /// `+`/`-` widen or narrow the time window when no text field has focus
+    fn handle_time_zoom_keys(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let wider_shortcut = self.shortcut("widen_time_window");
+        let narrower_shortcut = self.shortcut("narrow_time_window");
+        let (wider, narrower) = ctx.input_mut(|i| {
+            (
+                wider_shortcut.is_some_and(|shortcut| i.consume_shortcut(&shortcut))
+                    || i.key_pressed(egui::Key::Equals),
+                narrower_shortcut.is_some_and(|shortcut| i.consume_shortcut(&shortcut)),
+            )
+        });
+        if wider {
+            self.zoom_time_window(true);
+            self.save_settings();
+        } else if narrower {
+            self.zoom_time_window(false);
+            self.save_settings();
+        }
+    }
+
+    fn toggle_minimal_mode(&mut self, ctx: &egui::Context) {
+        self.minimal_mode = !self.minimal_mode;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(self.minimal_mode));
+    }
+
+    /// Essential controls revealed when hovering the top edge in minimal mode
+    fn show_minimal_mode_overlay(&mut self, ctx: &egui::Context) {
+        let near_top = ctx
+            .input(|i| i.pointer.hover_pos())
+            .is_some_and(|position| position.y < 48.0);
+        if !near_top {
+            return;
+        }
+
+        let mut exit = false;
+        egui::Area::new(egui::Id::new("minimal_mode_overlay"))
+            .anchor(egui::Align2::RIGHT_TOP, [-12.0, 8.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        let state = &mut self.tabs[self.active_tab].collection_state;
+                        let (label, color) = state.badge();
+                        ui.colored_label(color, format!("● {label}"));
+                        match *state {
+                            CollectionState::Tailing => {
+                                if ui.button("⏸").on_hover_text("Pause").clicked() {
+                                    *state = CollectionState::Paused;
+                                }
+                            }
+                            CollectionState::Paused => {
+                                if ui.button("▶").on_hover_text("Resume").clicked() {
+                                    *state = CollectionState::Tailing;
+                                }
+                            }
+                            _ => {}
+                        }
+                        ui.checkbox(&mut self.auto_scroll, "Auto-scroll");
+                        if ui.button("Exit minimal mode").clicked() {
+                            exit = true;
+                        }
+                    });
+                });
+            });
+
+        if exit {
+            self.toggle_minimal_mode(ctx);
+        }
+    }
+
+    /// Collapsible view of a parsed JSON value; objects and arrays nest,
+    /// scalars are shown inline next to their key
+    fn json_tree(ui: &mut egui::Ui, key: &str, value: &serde_json::Value, path: &str) {
+        let children: Vec<(String, &serde_json::Value)> = match value {
+            serde_json::Value::Object(map) => map
+                .iter()
+                .map(|(key, value)| (key.clone(), value))
+                .collect(),
+            serde_json::Value::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (format!("[{index}]"), value))
+                .collect(),
+            scalar => {
+                ui.horizontal(|ui| {
+                    ui.strong(key);
+                    ui.add(egui::Label::new(scalar.to_string()).selectable(true));
+                });
+                return;
+            }
+        };
+
+        let summary = match value {
+            serde_json::Value::Array(_) => format!("{key}  [{}]", children.len()),
+            _ => format!("{key}  {{{}}}", children.len()),
+        };
+        egui::CollapsingHeader::new(summary)
+            .id_source(path)
+            .default_open(!path.contains('/'))
+            .show(ui, |ui| {
+                for (child_key, child) in children {
+                    Self::json_tree(ui, &child_key, child, &format!("{path}/{child_key}"));
+                }
+            });
+    }
 
-                        if let Some(dt) = parsed_timestamp {
-                            let formatted_timestamp = dt.format("%Y-%m-%d %H:%M:%S").to_string();
-                            // Remove the timestamp from content to avoid duplication
-                            let cleaned_content =
-                                content.replace(timestamp_str, "").trim().to_string();
-                            return (Some(formatted_timestamp), cleaned_content);
-                        }
-                    }
+    fn show_detail_window(&mut self, ctx: &egui::Context) {
+        let Some(id) = self.detail_entry else {
+            return;
+        };
+        let Some(entry) = self.source().logs.iter().find(|entry| entry.id == id) else {
+            self.detail_entry = None;
+            return;
+        };
+
+        // The raw line keeps any timestamp that extraction cut out of the JSON
+        let json = [&entry.raw, &entry.content].into_iter().find_map(|text| {
+            let text = text.trim();
+            (text.starts_with('{') || text.starts_with('['))
+                .then(|| serde_json::from_str::<serde_json::Value>(text).ok())
+                .flatten()
+        });
+
+        let mut open = true;
+        egui::Window::new("Log Entry")
+            .open(&mut open)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                ui.strong(&entry.timestamp);
+                ui.separator();
+                ui.add(egui::Label::new(&entry.content).selectable(true));
+
+                if let Some(json) = &json {
+                    ui.separator();
+                    egui::ScrollArea::vertical()
+                        .id_source("detail_json")
+                        .max_height(400.0)
+                        .show(ui, |ui| {
+                            Self::json_tree(ui, "JSON", json, &format!("json-{id}"));
+                        });
+                } else if !entry.fields.is_empty() {
+                    ui.separator();
+                    egui::Grid::new("detail_fields")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (key, value) in &entry.fields {
+                                ui.strong(key);
+                                ui.label(value);
+                                ui.end_row();
+                            }
+                        });
                 }
-            }
+            });
+
+        if !open {
+            self.detail_entry = None;
         }
+    }
 
-        // No timestamp found, return original content
-        (None, content.to_string())
+    /// Remember the current buffer's contents to diff a later run against
+    fn take_snapshot(&mut self) {
+        self.snapshot = Some(
+            self.tabs[self.active_tab]
+                .logs
+                .iter()
+                .map(|entry| entry.content.trim().to_string())
+                .collect(),
+        );
+        self.notify(format!(
+            "Snapshot of {} lines taken",
+            self.source().logs.len()
+        ));
+    }
+
+    /// Line diff of the snapshot against the current buffer, comparing content
+    /// without timestamps
+    fn diff_against_snapshot(&self) -> Option<Vec<(similar::ChangeTag, String)>> {
+        let snapshot = self.snapshot.as_ref()?;
+        let old: Vec<&str> = snapshot.iter().map(String::as_str).collect();
+        let new: Vec<&str> = self
+            .source()
+            .logs
+            .iter()
+            .map(|entry| entry.content.trim())
+            .collect();
+        let diff = similar::TextDiff::configure()
+            .timeout(std::time::Duration::from_secs(2))
+            .diff_slices(&old, &new);
+        Some(
+            diff.iter_all_changes()
+                .map(|change| (change.tag(), change.value().to_string()))
+                .collect(),
+        )
     }
 
-    fn start_log_collection(&mut self) {
-        if self.log_thread_handle.is_some() {
+    fn show_diff_window(&mut self, ctx: &egui::Context) {
+        let Some(changes) = &self.snapshot_diff else {
             return;
+        };
+
+        let mut open = true;
+        egui::Window::new("Snapshot Diff")
+            .open(&mut open)
+            .default_width(700.0)
+            .default_height(500.0)
+            .show(ctx, |ui| {
+                let removed = changes
+                    .iter()
+                    .filter(|(tag, _)| *tag == similar::ChangeTag::Delete)
+                    .count();
+                let added = changes
+                    .iter()
+                    .filter(|(tag, _)| *tag == similar::ChangeTag::Insert)
+                    .count();
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{removed} only in snapshot, {added} only in current run"
+                    ));
+                    ui.checkbox(&mut self.diff_only_changes, "Only changes");
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        for (tag, line) in changes {
+                            let (prefix, color) = match tag {
+                                similar::ChangeTag::Delete => {
+                                    ("- ", Some(egui::Color32::from_rgb(230, 80, 80)))
+                                }
+                                similar::ChangeTag::Insert => {
+                                    ("+ ", Some(egui::Color32::from_rgb(80, 200, 120)))
+                                }
+                                similar::ChangeTag::Equal if self.diff_only_changes => continue,
+                                similar::ChangeTag::Equal => ("  ", None),
+                            };
+                            let text = egui::RichText::new(format!("{prefix}{line}")).monospace();
+                            ui.label(match color {
+                                Some(color) => text.color(color),
+                                None => text.weak(),
+                            });
+                        }
+                    });
+            });
+
+        if !open {
+            self.snapshot_diff = None;
         }
+    }
 
-        let (tx, rx) = mpsc::channel();
-        self.log_receiver = Some(rx);
-        self.is_loading = true;
+    /// Plain-language summary of the active filters and how many lines they leave
+    fn filter_summary(&self) -> String {
+        let mut parts = self.filter_descriptions();
+        parts.push(format!(
+            "{} of {} lines",
+            Self::format_count(self.source().visible_count),
+            Self::format_count(self.source().logs.len())
+        ));
+        parts.join(" · ")
+    }
 
-        let command = self.settings.log_command.clone();
+    /// One phrase per active filter, for the status line and exported recipes
+    fn filter_descriptions(&self) -> Vec<String> {
+        let mut parts = Vec::new();
 
-        let handle = thread::spawn(move || {
-            let parts: Vec<&str> = command.split_whitespace().collect();
-            if parts.is_empty() {
-                return;
-            }
+        if !self.selected_log_levels.is_empty() {
+            let mut levels: Vec<String> = self
+                .selected_log_levels
+                .iter()
+                .map(|level| level.to_uppercase())
+                .collect();
+            levels.sort();
+            let verb = match self.filter_mode {
+                FilterMode::IncludeSelected => "Showing",
+                FilterMode::ExcludeSelected => "Hiding",
+            };
+            parts.push(format!("{verb} {}", levels.join(", ")));
+        } else {
+            parts.push("Showing all levels".to_string());
+        }
+
+        if !self.search_text.is_empty() {
+            let scope = match self.search_scope {
+                SearchScope::Both => String::new(),
+                _ => format!(" in {}", self.search_scope.display_name().to_lowercase()),
+            };
+            parts.push(format!("matching '{}'{scope}", self.search_text));
+        }
 
-            let program = parts[0];
-            let args = &parts[1..];
+        let filter = &self.settings.time_filter;
+        let span = match &filter.mode {
+            TimeSpanMode::Disabled => None,
+            TimeSpanMode::Predefined(span) => Some(span.display_name().to_lowercase()),
+            TimeSpanMode::Custom => Some("custom range".to_string()),
+            TimeSpanMode::Relative => Some(format!(
+                "last {} {}",
+                filter.relative_amount,
+                filter.relative_unit.display_name()
+            )),
+        };
+        if let Some(span) = span {
+            parts.push(if filter.invert {
+                format!("outside {span}")
+            } else {
+                span
+            });
+        }
+
+        if self.only_new_since_mark {
+            parts.push(format!(
+                "new since {}",
+                self.new_since_mark.format("%H:%M:%S")
+            ));
+        }
+        if !self.settings.dedup_field.is_empty() {
+            parts.push(format!("one per {}", self.settings.dedup_field));
+        }
+        parts
+    }
 
-            let mut cmd = Command::new(program);
-            cmd.args(args).stdout(Stdio::piped()).stderr(Stdio::piped());
+    /// Add the time filter to commands that can filter by time themselves
+    fn with_time_flags(command: &str, range: Option<(NaiveDateTime, NaiveDateTime)>) -> String {
+        let Some((since, until)) = range else {
+            return command.to_string();
+        };
+        let args = shlex::split(command).unwrap_or_default();
+        let program = args
+            .first()
+            .and_then(|program| Path::new(program).file_name())
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+        let follows = Self::follows(&args);
+        let local = |time: NaiveDateTime| Local.from_local_datetime(&time).earliest();
 
-            if let Ok(mut child) = cmd.spawn() {
-                if let Some(stdout) = child.stdout.take() {
-                    let reader = BufReader::new(stdout);
-                    for line in reader.lines() {
-                        match line {
-                            Ok(line_content) => {
-                                if tx.send(line_content).is_err() {
-                                    break;
-                                }
-                            }
-                            Err(_) => break,
-                        }
-                    }
+        let mut flags = Vec::new();
+        match program {
+            "journalctl" if !command.contains("--since") => {
+                if let Some(since) = local(since) {
+                    flags.push(format!("--since=@{}", since.timestamp()));
+                }
+                if let Some(until) = local(until).filter(|_| !follows) {
+                    flags.push(format!("--until=@{}", until.timestamp()));
                 }
-
-                // Clean up the child process
-                let _ = child.wait();
             }
-        });
+            "docker" | "podman" if !command.contains("--since") => {
+                if let Some(since) = local(since) {
+                    flags.push(format!("--since={}", since.to_rfc3339()));
+                }
+                if let Some(until) = local(until).filter(|_| !follows) {
+                    flags.push(format!("--until={}", until.to_rfc3339()));
+                }
+            }
+            "kubectl" if !command.contains("--since") => {
+                if let Some(since) = local(since) {
+                    flags.push(format!("--since-time={}", since.to_rfc3339()));
+                }
+            }
+            _ => {}
+        }
 
-        self.log_thread_handle = Some(handle);
+        if flags.is_empty() {
+            command.to_string()
+        } else {
+            format!("{command} {}", flags.join(" "))
+        }
     }
 
-    fn stop_log_collection(&mut self) {
-        self.log_receiver = None;
-        if let Some(handle) = self.log_thread_handle.take() {
-            // Don't block the UI - let the thread finish naturally
-            std::mem::drop(handle);
+    /// Save the command and filters to `path` as a shell script (`.sh`) or a
+    /// JSON recipe so someone else can regenerate this view
+    fn export_recipe(&mut self, path: &Path) {
+        let command = Self::with_time_flags(
+            &Self::effective_command(&self.source().command),
+            self.get_time_range(),
+        );
+        let filters = self.filter_descriptions();
+        let content = if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            let recipe = serde_json::json!({
+                "command": command,
+                "filters": self.current_filters(),
+                "description": filters,
+            });
+            serde_json::to_string_pretty(&recipe).map_err(|err| err.to_string())
+        } else {
+            let mut script = String::from("#!/bin/sh\n");
+            script.push_str("# Filters applied in the log viewer on top of this output:\n");
+            for filter in &filters {
+                script.push_str(&format!("#   {filter}\n"));
+            }
+            script.push_str(&command);
+            script.push('\n');
+            Ok(script)
+        };
+
+        match content.and_then(|content| fs::write(path, content).map_err(|err| err.to_string())) {
+            Ok(()) => self.notify(format!("Saved recipe to {}", path.display())),
+            Err(err) => self.notify_error(format!("Could not save recipe: {err}")),
         }
     }
 
-    fn restart_log_collection(&mut self) {
-        self.stop_log_collection();
-        self.logs.clear();
-        self.is_loading = false;
-        self.start_log_collection();
+    /// Group digits in thousands: 8204 -> "8,204"
+    fn format_count(count: usize) -> String {
+        let digits = count.to_string();
+        let mut grouped = String::new();
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(digit);
+        }
+        grouped
     }
 
-    fn add_log_entry(&mut self, content: String) {
-        let (extracted_timestamp, cleaned_content) = Self::extract_timestamp_from_log(&content);
-
-        let timestamp = extracted_timestamp
-            .unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string());
+    /// How long a running stream has been silent, once past the idle timeout
+    fn stale_for(&self) -> Option<std::time::Duration> {
+        let source = self.source();
+        if self.settings.stale_after_secs == 0
+            || source.collection_state != CollectionState::Tailing
+        {
+            return None;
+        }
+        let silent = source.last_log_arrival?.elapsed();
+        (silent.as_secs() >= self.settings.stale_after_secs).then_some(silent)
+    }
 
-        self.logs.push(LogEntry {
-            timestamp,
-            content: cleaned_content,
-        });
+    /// Clear a stream that has been silent past the auto-clear timeout, once per
+    /// idle period, leaving a marker line. The lines stay restorable via Undo Clear.
+    fn auto_clear_if_idle(&mut self, tab: usize) {
+        let minutes = self.settings.auto_clear_idle_mins;
+        let source = &mut self.tabs[tab];
+        if minutes == 0
+            || source.idle_cleared
+            || source.logs.is_empty()
+            || source.collection_state == CollectionState::Paused
+        {
+            return;
+        }
+        let Some(last_arrival) = source.last_log_arrival else {
+            return;
+        };
+        if last_arrival.elapsed().as_secs() < minutes * 60 {
+            return;
+        }
 
-        // Set loading to false when we receive the first log entry
-        if self.is_loading {
-            self.is_loading = false;
+        // Joined with an earlier clear so undo still brings everything back
+        match &mut source.cleared_logs {
+            Some(cleared) => cleared.append(&mut source.logs),
+            None => source.cleared_logs = Some(std::mem::take(&mut source.logs)),
         }
+        source.idle_cleared = true;
+        self.push_marker(
+            tab,
+            format!("--- auto-cleared after {minutes} min idle ---"),
+        );
+    }
 
-        if self.logs.len() > 10000 {
-            self.logs.drain(0..1000);
+    /// Substring match where a leading `^` or trailing `$` pins the term to
+    /// the start or end of the text
+    fn anchored_contains(text: &str, query: &str) -> bool {
+        let (at_start, term) = match query.strip_prefix('^') {
+            Some(rest) => (true, rest),
+            None => (false, query),
+        };
+        let (at_end, term) = match term.strip_suffix('$') {
+            Some(rest) => (true, rest),
+            None => (false, term),
+        };
+        match (at_start, at_end) {
+            (true, true) => text == term,
+            (true, false) => text.starts_with(term),
+            (false, true) => text.ends_with(term),
+            (false, false) => text.contains(term),
         }
     }
 
     fn filtered_logs(&self) -> Vec<&LogEntry> {
-        self.logs
+        let mut logs: Vec<&LogEntry> = self
+            .source()
+            .logs
             .iter()
             .filter(|entry| {
                 let matches_filter = if self.selected_log_levels.is_empty() {
                     true
                 } else {
-                    let content_lower = entry.content.to_lowercase();
-
-                    let contains_selected_level = self
-                        .selected_log_levels
-                        .iter()
-                        .any(|level| content_lower.contains(&level.to_lowercase()));
+                    let contains_selected_level = if self.level_regex.is_some() {
+                        // The extracted level is authoritative; ignore keywords elsewhere
+                        entry.level.is_some_and(|entry_level| {
+                            self.selected_log_levels
+                                .iter()
+                                .any(|level| level.eq_ignore_ascii_case(entry_level))
+                        })
+                    } else {
+                        let content_lower = entry.content.to_lowercase();
+                        self.selected_log_levels
+                            .iter()
+                            .any(|level| content_lower.contains(&level.to_lowercase()))
+                    };
 
                     match self.filter_mode {
                         FilterMode::IncludeSelected => contains_selected_level,
@@ -515,22 +5626,27 @@ impl LogsApp {
 
                 let matches_search = if self.search_text.is_empty() {
                     true
+                } else if let Some(matched) = Self::match_field_query(entry, &self.search_text) {
+                    matched
                 } else {
-                    entry
-                        .content
-                        .to_lowercase()
-                        .contains(&self.search_text.to_lowercase())
-                        || entry
-                            .timestamp
-                            .to_lowercase()
-                            .contains(&self.search_text.to_lowercase())
+                    let search_lower = self.search_text.to_lowercase();
+                    let matches =
+                        |text: &str| Self::anchored_contains(&text.to_lowercase(), &search_lower);
+
+                    match self.search_scope {
+                        SearchScope::Content => matches(&entry.content),
+                        SearchScope::Timestamp => matches(&entry.timestamp),
+                        SearchScope::Both => matches(&entry.content) || matches(&entry.timestamp),
+                        SearchScope::RawLine => matches(&entry.raw),
+                    }
                 };
 
                 let matches_time = if let Some((from_time, to_time)) = self.get_time_range() {
                     let entry_time = Self::parse_time_input(&entry.timestamp);
 
                     if let Some(entry_dt) = entry_time {
-                        entry_dt >= from_time && entry_dt <= to_time
+                        let in_range = entry_dt >= from_time && entry_dt <= to_time;
+                        in_range != self.settings.time_filter.invert
                     } else {
                         true
                     }
@@ -538,32 +5654,257 @@ impl LogsApp {
                     true
                 };
 
-                matches_filter && matches_search && matches_time
+                // Lines without a parseable time arrived after the mark by definition
+                let matches_since_mark = !self.only_new_since_mark
+                    || Self::parse_time_input(&entry.timestamp)
+                        .is_none_or(|entry_time| entry_time >= self.new_since_mark);
+
+                matches_filter && matches_search && matches_time && matches_since_mark
             })
-            .collect()
+            .collect();
+
+        if self.settings.sort_by_timestamp {
+            // Unparseable lines borrow the time of the line before them, so the
+            // stable sort keeps them next to it
+            let mut last_time = None;
+            let mut keyed: Vec<(Option<NaiveDateTime>, &LogEntry)> = logs
+                .into_iter()
+                .map(|entry| {
+                    if let Some(time) = Self::parse_time_input(&entry.timestamp) {
+                        last_time = Some(time);
+                    }
+                    (last_time, entry)
+                })
+                .collect();
+            keyed.sort_by_key(|(time, _)| *time);
+            logs = keyed.into_iter().map(|(_, entry)| entry).collect();
+        }
+
+        if !self.settings.dedup_field.is_empty() {
+            let window = Duration::seconds(self.settings.dedup_window_secs);
+            let mut last_shown: HashMap<String, NaiveDateTime> = HashMap::new();
+            logs.retain(|entry| {
+                let Some(key) = entry.field(&self.settings.dedup_field) else {
+                    return true;
+                };
+                let Some(time) = Self::parse_time_input(&entry.timestamp) else {
+                    return true;
+                };
+                match last_shown.get(key) {
+                    Some(&shown) if (time - shown).abs() < window => false,
+                    _ => {
+                        last_shown.insert(key.to_string(), time);
+                        true
+                    }
+                }
+            });
+        }
+
+        logs
     }
 }
 
-impl eframe::App for LogsApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+impl LogsApp {
+    /// Take in what the collection thread of tab `tab` sent since the last frame
+    fn drain_collector(&mut self, tab: usize, ctx: &egui::Context) {
         let mut new_logs = Vec::new();
-        if let Some(receiver) = &self.log_receiver {
-            while let Ok(log_line) = receiver.try_recv() {
-                new_logs.push(log_line);
+        let mut collection_error = None;
+        let mut import_bytes_read = None;
+        let mut disconnected = false;
+        let mut exit_code = None;
+        let source = &mut self.tabs[tab];
+        if let Some(receiver) = &source.log_receiver
+            && source.collection_state != CollectionState::Paused
+        {
+            loop {
+                // Leave the rest of a burst for the next frames so input stays responsive
+                if new_logs.len() >= MAX_LINES_PER_FRAME {
+                    ctx.request_repaint();
+                    break;
+                }
+                match receiver.try_recv() {
+                    Ok(CollectorMessage::Line(log_line)) => new_logs.push((log_line, false)),
+                    Ok(CollectorMessage::StderrLine(log_line)) => new_logs.push((log_line, true)),
+                    Ok(CollectorMessage::Error(message)) => collection_error = Some(message),
+                    Ok(CollectorMessage::SpawnFailed(message)) => {
+                        source.process_status = Some(ProcessStatus::Failed);
+                        source.last_error = Some(message.clone());
+                        collection_error = Some(message);
+                    }
+                    Ok(CollectorMessage::Progress(bytes_read)) => {
+                        import_bytes_read = Some(bytes_read)
+                    }
+                    Ok(CollectorMessage::Started) => {
+                        source.spawned_at = Some(Instant::now());
+                        source.process_status = Some(ProcessStatus::Running);
+                    }
+                    Ok(CollectorMessage::Exited(code)) => {
+                        source.process_status = Some(ProcessStatus::Exited(code));
+                        exit_code = Some(code);
+                    }
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        disconnected = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        if !new_logs.is_empty() {
+            source.idle_cleared = false;
+        }
+        for (log_line, is_stderr) in new_logs {
+            self.add_log_entry(tab, log_line, is_stderr);
+        }
+        // Only the tab on screen beeps or flashes
+        let arrived = std::mem::take(&mut self.tabs[tab].new_cue_groups);
+        if tab == self.active_tab {
+            self.play_level_cues(arrived);
+        }
+        self.auto_clear_if_idle(tab);
+        if let Some(code) = exit_code {
+            let source = &mut self.tabs[tab];
+            if code == Some(SSH_CONNECTION_FAILED)
+                && let Some(target) = &source.ssh
+            {
+                source.last_error = Some(format!(
+                    "Couldn't reach {} over ssh, or the connection dropped",
+                    target.host
+                ));
             }
+            self.handle_command_exit(tab, code);
         }
 
-        for log_line in new_logs {
-            self.add_log_entry(log_line);
+        let source = &mut self.tabs[tab];
+        if let (Some(bytes_read), Some((done, _))) =
+            (import_bytes_read, source.import_progress.as_mut())
+        {
+            *done = bytes_read;
         }
 
+        // A quiet follower is running fine; stop showing it as starting up
+        if source.is_loading
+            && self.settings.loading_timeout_secs > 0
+            && source.spawned_at.is_some_and(|spawned| {
+                spawned.elapsed().as_secs() >= self.settings.loading_timeout_secs
+            })
+        {
+            source.is_loading = false;
+        }
+
+        if let Some(message) = collection_error {
+            source.collection_state = CollectionState::Error(message.clone());
+            source.is_loading = false;
+            self.notify_error(self.tab_message(tab, message));
+        }
+
+        if disconnected {
+            // The collection thread has finished, so the stream is over
+            let source = &mut self.tabs[tab];
+            source.log_receiver = None;
+            source.log_thread_handle = None;
+            source.is_loading = false;
+            if source.collection_state == CollectionState::Tailing {
+                let message = source
+                    .import_progress
+                    .is_some()
+                    .then(|| format!("Loaded {} lines from file", source.logs.len()));
+                source.collection_state = CollectionState::Stopped;
+                if let Some(message) = message {
+                    self.notify(self.tab_message(tab, message));
+                }
+            }
+            self.tabs[tab].import_progress = None;
+        }
+
+        // Last, so the disconnect above can't drop the restarted collection
+        if self.tabs[tab]
+            .restart_at
+            .is_some_and(|restart_at| Instant::now() >= restart_at)
+        {
+            self.restart_after_exit(tab);
+        }
+    }
+
+    /// Everything `update` does; split out because tests can't build an `eframe::Frame`
+    fn run_frame(&mut self, ctx: &egui::Context) {
+        self.drain_background_tabs(ctx);
+        self.drain_collector(self.active_tab, ctx);
+
         ctx.request_repaint_after(std::time::Duration::from_millis(
             self.settings.refresh_interval,
         ));
 
-        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+        self.evict_expired_entries();
+        self.update_error_burst();
+
+        if let Some(rect) = ctx.input(|i| i.viewport().inner_rect) {
+            self.settings.window_size = Some([rect.width(), rect.height()]);
+        }
+
+        let minimal_shortcut = self.shortcut("toggle_minimal_mode");
+        if minimal_shortcut.is_some_and(|shortcut| ctx.input_mut(|i| i.consume_shortcut(&shortcut)))
+        {
+            self.toggle_minimal_mode(ctx);
+        }
+
+        egui::TopBottomPanel::top("top_panel").show_animated(ctx, !self.minimal_mode, |ui| {
             egui::menu::bar(ui, |ui| {
                 ui.menu_button("File", |ui| {
+                    if ui.button("Open log file…").clicked() {
+                        self.open_file_dialog(FileDialogPurpose::OpenLogFile, || {
+                            rfd::FileDialog::new()
+                                .add_filter("Log files", &["log", "txt", "gz"])
+                                .add_filter("All files", &["*"])
+                                .pick_file()
+                        });
+                        ui.close_menu();
+                    }
+                    if ui.button("Listen for syslog…").clicked() {
+                        self.syslog_window_open = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("New tab").clicked() {
+                        self.add_tab();
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(
+                            *DOCKER_AVAILABLE,
+                            egui::Button::new("Pick Docker container…"),
+                        )
+                        .on_disabled_hover_text("docker isn't installed, or isn't on the PATH")
+                        .clicked()
+                    {
+                        self.open_docker_picker();
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(
+                            *KUBECTL_AVAILABLE,
+                            egui::Button::new("Pick Kubernetes pod…"),
+                        )
+                        .on_disabled_hover_text("kubectl isn't installed, or isn't on the PATH")
+                        .clicked()
+                    {
+                        self.pod_picker.get_or_insert_with(PodPicker::default);
+                        self.load_pods();
+                        ui.close_menu();
+                    }
+                    if ui.button("Build journalctl command…").clicked() {
+                        self.journalctl_form
+                            .get_or_insert_with(JournalctlForm::default);
+                        ui.close_menu();
+                    }
+                    if ui.button("Group by field…").clicked() {
+                        self.group_by.get_or_insert_with(GroupBySummary::default);
+                        ui.close_menu();
+                    }
+                    if ui.button("Open files matching pattern…").clicked() {
+                        self.glob_pattern_input.get_or_insert_with(String::new);
+                        ui.close_menu();
+                    }
                     if ui.button("Settings").clicked() {
                         self.show_settings = !self.show_settings;
                     }
@@ -571,21 +5912,225 @@ impl eframe::App for LogsApp {
                         self.show_favorites = !self.show_favorites;
                     }
                     if ui.button("Clear Logs").clicked() {
-                        self.logs.clear();
+                        self.clear_logs();
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.source().cleared_logs.is_some(),
+                            egui::Button::new("Undo Clear"),
+                        )
+                        .clicked()
+                    {
+                        self.undo_clear_logs();
+                        ui.close_menu();
                     }
                     if ui.button("Restart Collection").clicked() {
                         self.restart_log_collection();
                     }
+                    let running = matches!(
+                        self.source().collection_state,
+                        CollectionState::Tailing | CollectionState::Paused
+                    );
+                    if ui
+                        .add_enabled(running, egui::Button::new("Stop Collection"))
+                        .clicked()
+                    {
+                        self.stop_command();
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(!running, egui::Button::new("Start Collection"))
+                        .clicked()
+                    {
+                        self.start_command();
+                        ui.close_menu();
+                    }
+                    if ui.button("Minimal Mode (F11)").clicked() {
+                        self.toggle_minimal_mode(ui.ctx());
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button("Copy as Markdown").clicked() {
+                        let entries = self.filtered_logs();
+                        let count = entries.len();
+                        let markdown = Self::logs_as_markdown(&entries);
+                        ui.output_mut(|o| o.copied_text = markdown);
+                        self.notify(format!("Copied {count} lines as Markdown"));
+                        ui.close_menu();
+                    }
+                    if ui.button("Copy as text").clicked() {
+                        let entries = self.filtered_logs();
+                        let total = entries.len();
+                        let (text, count) = Self::logs_as_text(&entries, COPY_TEXT_MAX_BYTES);
+                        ui.output_mut(|o| o.copied_text = text);
+                        if count < total {
+                            self.notify(format!(
+                                "Copied the last {count} of {total} lines; \
+                                 the rest exceed the {} MB clipboard cap",
+                                COPY_TEXT_MAX_BYTES / (1024 * 1024)
+                            ));
+                        } else {
+                            self.notify(format!("Copied {count} lines"));
+                        }
+                        ui.close_menu();
+                    }
+                    if ui.button("Export command recipe…").clicked() {
+                        ui.close_menu();
+                        self.open_file_dialog(FileDialogPurpose::ExportRecipe, || {
+                            rfd::FileDialog::new()
+                                .add_filter("Shell script", &["sh"])
+                                .add_filter("JSON recipe", &["json"])
+                                .set_file_name("logs-recipe.sh")
+                                .save_file()
+                        });
+                    }
+                    if ui.button("Save screenshot of logs…").clicked() {
+                        ui.close_menu();
+                        self.request_screenshot(true);
+                    }
+                    if ui.button("Save screenshot of window…").clicked() {
+                        ui.close_menu();
+                        self.request_screenshot(false);
+                    }
+                    ui.separator();
+                    if ui.button("Take snapshot").clicked() {
+                        self.take_snapshot();
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.snapshot.is_some(),
+                            egui::Button::new("Compare with snapshot"),
+                        )
+                        .clicked()
+                    {
+                        self.snapshot_diff = self.diff_against_snapshot();
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button("Copy settings path").clicked() {
+                        let path = Self::get_config_path().display().to_string();
+                        ui.output_mut(|o| o.copied_text = path.clone());
+                        self.notify(format!("Copied {path}"));
+                        ui.close_menu();
+                    }
+                    if ui.button("Open config folder").clicked() {
+                        self.open_config_folder();
+                        ui.close_menu();
+                    }
+                    if ui.button("Reload settings").clicked() {
+                        self.reload_settings(ui.ctx());
+                        ui.close_menu();
+                    }
                 });
 
                 ui.separator();
 
-                ui.label("Command:");
-                ui.add(
-                    egui::TextEdit::singleline(&mut self.settings.log_command).desired_width(200.0),
-                );
-                if ui.button("Apply").clicked() {
-                    self.restart_log_collection();
+                if self.source().reading_stdin {
+                    ui.label("Command:");
+                    let mut stdin_label = STDIN_LABEL.to_string();
+                    ui.add_enabled(
+                        false,
+                        egui::TextEdit::singleline(&mut stdin_label).desired_width(200.0),
+                    )
+                    .on_disabled_hover_text(
+                        "Reading lines piped into the app; stop it to run a command instead",
+                    );
+                } else {
+                    let source = self.source_mut();
+                    if ui
+                        .toggle_value(&mut source.follow_file, "File")
+                        .on_hover_text("Follow the file at this path instead of running a command")
+                        .changed()
+                    {
+                        if source.follow_file {
+                            source.ssh = None;
+                        }
+                        self.save_settings();
+                    }
+                    let mut use_ssh = self.source().ssh.is_some();
+                    if ui
+                        .toggle_value(&mut use_ssh, "SSH")
+                        .on_hover_text(
+                            "Run the command on a remote host; enable auto-restart in \
+                             Settings to reconnect when the connection drops",
+                        )
+                        .changed()
+                    {
+                        let source = self.source_mut();
+                        source.ssh = use_ssh.then(SshTarget::default);
+                        if use_ssh {
+                            source.follow_file = false;
+                        }
+                        self.save_settings();
+                    }
+                    let mut ssh_changed = false;
+                    if let Some(target) = self.source_mut().ssh.as_mut() {
+                        ssh_changed |= ui
+                            .add(
+                                egui::TextEdit::singleline(&mut target.user)
+                                    .hint_text("user")
+                                    .desired_width(60.0),
+                            )
+                            .changed();
+                        ui.label("@");
+                        ssh_changed |= ui
+                            .add(
+                                egui::TextEdit::singleline(&mut target.host)
+                                    .hint_text("host")
+                                    .desired_width(100.0),
+                            )
+                            .changed();
+                        ui.label(":");
+                        ssh_changed |= ui
+                            .add(egui::DragValue::new(&mut target.port).range(1..=65535))
+                            .changed();
+                    }
+                    if ssh_changed {
+                        self.save_settings();
+                    }
+                    ui.label(if self.source().follow_file {
+                        "Path:"
+                    } else if self.source().ssh.is_some() {
+                        "Remote command:"
+                    } else {
+                        "Command:"
+                    });
+                    // Multiline so a pasted script keeps its lines; `effective_command`
+                    // joins them into what actually runs
+                    ui.add(
+                        egui::TextEdit::multiline(&mut self.source_mut().command)
+                            .id(egui::Id::new(COMMAND_INPUT_ID))
+                            .desired_rows(1)
+                            .desired_width(200.0),
+                    );
+                    if ui.button("Apply").clicked() {
+                        self.run_command(self.source().command.clone(), None);
+                    }
+                }
+                if (self.source().ssh.is_some() || Self::is_remote_command(&self.source().command))
+                    && ui
+                        .button("Reconnect")
+                        .on_hover_text("Restart the connection and keep the current logs")
+                        .clicked()
+                {
+                    self.reconnect_log_collection(self.active_tab);
+                }
+                let command = &self.source().command;
+                let effective_command = Self::effective_command(command);
+                if effective_command != *command {
+                    ui.weak("(flattened)")
+                        .on_hover_text(format!("Runs as: {effective_command}"));
+                }
+                if let Some(with_backlog) =
+                    Self::with_backlog(&effective_command, self.settings.backlog_lines)
+                {
+                    ui.weak(format!(
+                        "(+{} lines of history)",
+                        self.settings.backlog_lines
+                    ))
+                    .on_hover_text(format!("Starts as: {with_backlog}"));
                 }
                 if ui.button("⭐").on_hover_text("Save as favorite").clicked() {
                     self.new_favorite_name =
@@ -645,17 +6190,55 @@ impl eframe::App for LogsApp {
                     );
                 });
 
+                if ui
+                    .button("✖ Clear filters")
+                    .on_hover_text("Show everything; the command and settings are kept")
+                    .clicked()
+                {
+                    self.clear_filters();
+                }
+                if ui
+                    .button("✖ Levels & search")
+                    .on_hover_text("Clear the level and search filters but keep the time window")
+                    .clicked()
+                {
+                    self.clear_content_filters();
+                }
+
                 ui.separator();
 
                 ui.label("Search:");
-                ui.text_edit_singleline(&mut self.search_text);
+                // Fixed ids keep focus even when widgets before these come and go
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.search_text)
+                        .id(egui::Id::new(SEARCH_INPUT_ID)),
+                )
+                .on_hover_text(
+                    "Text to find (^term for line start, term$ for line end), \
+                     or a field query like status=500 or latency>200",
+                );
+                egui::ComboBox::from_id_source("search_scope")
+                    .selected_text(self.search_scope.display_name())
+                    .width(90.0)
+                    .show_ui(ui, |ui| {
+                        for scope in [
+                            SearchScope::Content,
+                            SearchScope::Timestamp,
+                            SearchScope::Both,
+                            SearchScope::RawLine,
+                        ] {
+                            let name = scope.display_name();
+                            ui.selectable_value(&mut self.search_scope, scope, name);
+                        }
+                    });
 
                 ui.separator();
 
                 ui.label("Time Filter:");
+                let time_filter_before = self.settings.time_filter.clone();
                 ui.horizontal(|ui| {
                     egui::ComboBox::from_label("Time Span")
-                        .selected_text(match &self.time_span_mode {
+                        .selected_text(match &self.settings.time_filter.mode {
                             TimeSpanMode::Disabled => "Disabled",
                             TimeSpanMode::Predefined(span) => span.display_name(),
                             TimeSpanMode::Custom => "Custom Range",
@@ -663,145 +6246,190 @@ impl eframe::App for LogsApp {
                         })
                         .show_ui(ui, |ui| {
                             ui.selectable_value(
-                                &mut self.time_span_mode,
+                                &mut self.settings.time_filter.mode,
                                 TimeSpanMode::Disabled,
                                 "Disabled",
                             );
                             ui.separator();
 
                             ui.selectable_value(
-                                &mut self.time_span_mode,
+                                &mut self.settings.time_filter.mode,
                                 TimeSpanMode::Predefined(PredefinedSpan::Last15Minutes),
                                 "Last 15 minutes",
                             );
                             ui.selectable_value(
-                                &mut self.time_span_mode,
+                                &mut self.settings.time_filter.mode,
                                 TimeSpanMode::Predefined(PredefinedSpan::Last30Minutes),
                                 "Last 30 minutes",
                             );
                             ui.selectable_value(
-                                &mut self.time_span_mode,
+                                &mut self.settings.time_filter.mode,
                                 TimeSpanMode::Predefined(PredefinedSpan::Last1Hour),
                                 "Last 1 hour",
                             );
                             ui.selectable_value(
-                                &mut self.time_span_mode,
+                                &mut self.settings.time_filter.mode,
                                 TimeSpanMode::Predefined(PredefinedSpan::Last6Hours),
                                 "Last 6 hours",
                             );
                             ui.selectable_value(
-                                &mut self.time_span_mode,
+                                &mut self.settings.time_filter.mode,
                                 TimeSpanMode::Predefined(PredefinedSpan::Last24Hours),
                                 "Last 24 hours",
                             );
                             ui.selectable_value(
-                                &mut self.time_span_mode,
+                                &mut self.settings.time_filter.mode,
                                 TimeSpanMode::Predefined(PredefinedSpan::Last3Days),
                                 "Last 3 days",
                             );
                             ui.selectable_value(
-                                &mut self.time_span_mode,
+                                &mut self.settings.time_filter.mode,
                                 TimeSpanMode::Predefined(PredefinedSpan::Last1Week),
                                 "Last 1 week",
                             );
                             ui.selectable_value(
-                                &mut self.time_span_mode,
+                                &mut self.settings.time_filter.mode,
                                 TimeSpanMode::Predefined(PredefinedSpan::Last1Month),
                                 "Last 1 month",
                             );
                             ui.separator();
 
                             ui.selectable_value(
-                                &mut self.time_span_mode,
+                                &mut self.settings.time_filter.mode,
                                 TimeSpanMode::Custom,
                                 "Custom Range",
                             );
                             ui.selectable_value(
-                                &mut self.time_span_mode,
+                                &mut self.settings.time_filter.mode,
                                 TimeSpanMode::Relative,
                                 "Relative Time",
                             );
                         });
+                    ui.add_enabled(
+                        self.settings.time_filter.mode != TimeSpanMode::Disabled,
+                        egui::Checkbox::new(&mut self.settings.time_filter.invert, "Invert"),
+                    )
+                    .on_hover_text("Show entries outside the range, e.g. clock-skewed lines");
+
+                    let zoomable = matches!(
+                        self.settings.time_filter.mode,
+                        TimeSpanMode::Predefined(_) | TimeSpanMode::Relative
+                    );
+                    if ui
+                        .add_enabled(zoomable, egui::Button::new("−").small())
+                        .on_hover_text("Narrow the window (-)")
+                        .clicked()
+                    {
+                        self.zoom_time_window(false);
+                    }
+                    if ui
+                        .add_enabled(zoomable, egui::Button::new("+").small())
+                        .on_hover_text("Widen the window (+)")
+                        .clicked()
+                    {
+                        self.zoom_time_window(true);
+                    }
                 });
 
-                match &self.time_span_mode {
+                match &self.settings.time_filter.mode {
                     TimeSpanMode::Custom => {
                         ui.horizontal(|ui| {
                             ui.label("From:");
                             ui.add(
-                                egui::DragValue::new(&mut self.custom_from_year)
-                                    .range(2000..=2100)
-                                    .prefix("Year: "),
+                                egui::DragValue::new(
+                                    &mut self.settings.time_filter.custom_from_year,
+                                )
+                                .range(2000..=2100)
+                                .prefix("Year: "),
                             );
                             ui.add(
-                                egui::DragValue::new(&mut self.custom_from_month)
-                                    .range(1..=12)
-                                    .prefix("Month: "),
+                                egui::DragValue::new(
+                                    &mut self.settings.time_filter.custom_from_month,
+                                )
+                                .range(1..=12)
+                                .prefix("Month: "),
                             );
                             ui.add(
-                                egui::DragValue::new(&mut self.custom_from_day)
-                                    .range(1..=31)
-                                    .prefix("Day: "),
+                                egui::DragValue::new(
+                                    &mut self.settings.time_filter.custom_from_day,
+                                )
+                                .range(1..=31)
+                                .prefix("Day: "),
                             );
                             ui.add(
-                                egui::DragValue::new(&mut self.custom_from_hour)
-                                    .range(0..=23)
-                                    .prefix("Hour: "),
+                                egui::DragValue::new(
+                                    &mut self.settings.time_filter.custom_from_hour,
+                                )
+                                .range(0..=23)
+                                .prefix("Hour: "),
                             );
                             ui.add(
-                                egui::DragValue::new(&mut self.custom_from_minute)
-                                    .range(0..=59)
-                                    .prefix("Min: "),
+                                egui::DragValue::new(
+                                    &mut self.settings.time_filter.custom_from_minute,
+                                )
+                                .range(0..=59)
+                                .prefix("Min: "),
                             );
                         });
                         ui.horizontal(|ui| {
                             ui.label("To:");
                             ui.add(
-                                egui::DragValue::new(&mut self.custom_to_year)
+                                egui::DragValue::new(&mut self.settings.time_filter.custom_to_year)
                                     .range(2000..=2100)
                                     .prefix("Year: "),
                             );
                             ui.add(
-                                egui::DragValue::new(&mut self.custom_to_month)
-                                    .range(1..=12)
-                                    .prefix("Month: "),
+                                egui::DragValue::new(
+                                    &mut self.settings.time_filter.custom_to_month,
+                                )
+                                .range(1..=12)
+                                .prefix("Month: "),
                             );
                             ui.add(
-                                egui::DragValue::new(&mut self.custom_to_day)
+                                egui::DragValue::new(&mut self.settings.time_filter.custom_to_day)
                                     .range(1..=31)
                                     .prefix("Day: "),
                             );
                             ui.add(
-                                egui::DragValue::new(&mut self.custom_to_hour)
+                                egui::DragValue::new(&mut self.settings.time_filter.custom_to_hour)
                                     .range(0..=23)
                                     .prefix("Hour: "),
                             );
                             ui.add(
-                                egui::DragValue::new(&mut self.custom_to_minute)
-                                    .range(0..=59)
-                                    .prefix("Min: "),
+                                egui::DragValue::new(
+                                    &mut self.settings.time_filter.custom_to_minute,
+                                )
+                                .range(0..=59)
+                                .prefix("Min: "),
                             );
                         });
                     }
                     TimeSpanMode::Relative => {
                         ui.horizontal(|ui| {
                             ui.label("Last");
-                            ui.add(egui::DragValue::new(&mut self.relative_amount).range(1..=999));
+                            ui.add(
+                                egui::DragValue::new(
+                                    &mut self.settings.time_filter.relative_amount,
+                                )
+                                .range(1..=999),
+                            );
                             egui::ComboBox::from_label("")
-                                .selected_text(self.relative_unit.display_name())
+                                .selected_text(
+                                    self.settings.time_filter.relative_unit.display_name(),
+                                )
                                 .show_ui(ui, |ui| {
                                     ui.selectable_value(
-                                        &mut self.relative_unit,
+                                        &mut self.settings.time_filter.relative_unit,
                                         TimeUnit::Minutes,
                                         "minutes",
                                     );
                                     ui.selectable_value(
-                                        &mut self.relative_unit,
+                                        &mut self.settings.time_filter.relative_unit,
                                         TimeUnit::Hours,
                                         "hours",
                                     );
                                     ui.selectable_value(
-                                        &mut self.relative_unit,
+                                        &mut self.settings.time_filter.relative_unit,
                                         TimeUnit::Days,
                                         "days",
                                     );
@@ -810,60 +6438,999 @@ impl eframe::App for LogsApp {
                     }
                     _ => {}
                 }
+                if self.settings.time_filter != time_filter_before {
+                    self.save_settings();
+                }
 
                 ui.separator();
 
+                ui.checkbox(&mut self.only_new_since_mark, "Only new")
+                    .on_hover_text(format!(
+                        "Hide lines stamped before {}",
+                        self.new_since_mark.format("%H:%M:%S")
+                    ));
+                if ui
+                    .small_button("⟳")
+                    .on_hover_text("Move the mark to now")
+                    .clicked()
+                {
+                    self.new_since_mark = Local::now().naive_local();
+                    self.only_new_since_mark = true;
+                }
+
                 ui.checkbox(&mut self.auto_scroll, "Auto-scroll");
+                if self
+                    .settings
+                    .level_cues
+                    .values()
+                    .any(|cue| cue.sound || cue.flash)
+                {
+                    let (icon, hint) = if self.settings.cues_muted {
+                        ("🔕", "Unmute level cues")
+                    } else {
+                        ("🔔", "Mute level cues")
+                    };
+                    if ui.button(icon).on_hover_text(hint).clicked() {
+                        self.settings.cues_muted = !self.settings.cues_muted;
+                        self.save_settings();
+                    }
+                }
+
+                match self.source().collection_state {
+                    CollectionState::Tailing => {
+                        if ui.button("⏸").on_hover_text("Pause").clicked() {
+                            self.source_mut().collection_state = CollectionState::Paused;
+                        }
+                    }
+                    CollectionState::Paused => {
+                        if ui.button("▶").on_hover_text("Resume").clicked() {
+                            self.source_mut().collection_state = CollectionState::Tailing;
+                        }
+                    }
+                    _ => {
+                        if ui
+                            .button("⏵ Start")
+                            .on_hover_text("Run the command again, keeping the logs")
+                            .clicked()
+                        {
+                            self.start_command();
+                        }
+                    }
+                }
+                if matches!(
+                    self.source().collection_state,
+                    CollectionState::Tailing | CollectionState::Paused
+                ) && ui
+                    .button("⏹")
+                    .on_hover_text("Stop the command, keeping the logs")
+                    .clicked()
+                {
+                    self.stop_command();
+                }
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    ui.label(format!("Logs: {}", self.logs.len()));
+                    let source = self.source();
+                    ui.label(format!("Logs: {}", source.logs.len()));
+                    if let Some(status) = source.process_status {
+                        let (text, color) = status.badge();
+                        let badge = ui.colored_label(color, text);
+                        if let Some(command) = &source.running_command {
+                            badge.on_hover_text(command);
+                        }
+                    }
+
+                    if let Some((bytes_read, total)) = source.import_progress {
+                        let progress = if total > 0 {
+                            egui::ProgressBar::new(bytes_read as f32 / total as f32)
+                                .show_percentage()
+                        } else {
+                            egui::ProgressBar::new(0.0)
+                                .text(format!("{} KB read", bytes_read / 1024))
+                        };
+                        ui.add(progress.desired_width(120.0));
+                    }
+
+                    let (label, color) = source.collection_state.badge();
+                    let color = if source.in_error_burst {
+                        // Blink between the state color and red while errors spike
+                        let blink = (ui.input(|i| i.time) * 2.0) as i64 % 2 == 0;
+                        ui.ctx()
+                            .request_repaint_after(std::time::Duration::from_millis(250));
+                        if blink {
+                            egui::Color32::from_rgb(230, 80, 80)
+                        } else {
+                            color
+                        }
+                    } else {
+                        color
+                    };
+                    let label = if source.in_error_burst {
+                        format!("● {label} 🔥 error burst")
+                    } else {
+                        format!("● {label}")
+                    };
+                    let badge = ui.colored_label(color, label);
+                    if let CollectionState::Error(message) = &source.collection_state {
+                        badge.on_hover_text(message);
+                    }
+
+                    if source.trimmed_lines > 0 {
+                        ui.weak(format!("✂ {} trimmed", source.trimmed_lines))
+                            .on_hover_text(format!(
+                                "The buffer is capped at {} lines and the oldest are dropped; \
+                                 raise the cap in Settings or filter at ingest to keep more",
+                                self.settings.max_log_entries
+                            ));
+                    }
+
+                    if self.settings.sample_every > 1 {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(230, 190, 60),
+                            format!("sampling 1:{}", self.settings.sample_every),
+                        )
+                        .on_hover_text(format!(
+                            "Keeping one in {} lines; {} lines are always kept",
+                            self.settings.sample_every,
+                            self.settings.sample_keep_levels.join("/")
+                        ));
+                    }
+
+                    if let Some(silent) = self.stale_for() {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(230, 190, 60),
+                            format!("⚠ no logs for {}s", silent.as_secs()),
+                        )
+                        .on_hover_text("The command may have stalled or lost its connection");
+                    }
                 });
             });
         });
 
         let mut show_settings = self.show_settings;
         let mut show_favorites = self.show_favorites;
-        let mut apply_settings = false;
         let mut reset_settings = false;
 
-        if show_settings {
+        if show_settings && !self.minimal_mode {
             egui::Window::new("Settings")
                 .open(&mut show_settings)
                 .show(ctx, |ui| {
                     ui.label("Refresh Interval (ms):");
+                    // Only repaint timing depends on this, so it applies live
+                    // and is saved once the slider is released
+                    let response = ui.add(egui::Slider::new(
+                        &mut self.settings.refresh_interval,
+                        100..=5000,
+                    ));
+                    if response.drag_stopped() || (response.changed() && !response.dragged()) {
+                        self.save_settings();
+                    }
+
+                    if ui
+                        .checkbox(
+                            &mut self.settings.clear_on_restart,
+                            "Clear logs when collection restarts",
+                        )
+                        .changed()
+                    {
+                        self.save_settings();
+                    }
+
+                    let shell = if cfg!(windows) { "cmd /C" } else { "sh -c" };
+                    if ui
+                        .checkbox(
+                            &mut self.settings.use_shell,
+                            format!("Run commands through the shell ({shell})"),
+                        )
+                        .changed()
+                    {
+                        self.save_settings();
+                    }
+                    if self.settings.use_shell {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(230, 190, 60),
+                            "Pipes and redirects work, but so does anything else the shell \
+                             can do: only run commands and favorites you trust",
+                        );
+                    }
+
+                    ui.label("Working directory (empty uses the app's):")
+                        .on_hover_text("Over ssh, this directory is on the remote host");
+                    ui.horizontal(|ui| {
+                        let mut dir = self
+                            .settings
+                            .working_dir
+                            .as_ref()
+                            .map(|dir| dir.display().to_string())
+                            .unwrap_or_default();
+                        let dir_changed = ui
+                            .add(
+                                egui::TextEdit::singleline(&mut dir)
+                                    .hint_text("e.g. /var/log/app")
+                                    .desired_width(240.0),
+                            )
+                            .changed();
+                        if ui.button("Browse…").clicked() {
+                            self.open_file_dialog(FileDialogPurpose::WorkingDir, || {
+                                rfd::FileDialog::new().pick_folder()
+                            });
+                        }
+                        if dir_changed {
+                            self.settings.working_dir =
+                                (!dir.trim().is_empty()).then(|| PathBuf::from(dir.trim()));
+                            self.save_settings();
+                        }
+                        if self
+                            .settings
+                            .working_dir
+                            .as_ref()
+                            .is_some_and(|dir| !dir.is_dir())
+                        {
+                            ui.colored_label(egui::Color32::from_rgb(230, 80, 80), "not found");
+                        }
+                    });
+
+                    ui.label("Environment variables for the command:")
+                        .on_hover_text("Over ssh, these are exported on the remote host");
+                    let mut env_changed = false;
+                    let mut env_to_remove = None;
+                    for (index, (key, value)) in self.settings.env.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            env_changed |= ui
+                                .add(
+                                    egui::TextEdit::singleline(key)
+                                        .hint_text("RUST_LOG")
+                                        .desired_width(120.0),
+                                )
+                                .changed();
+                            ui.label("=");
+                            env_changed |= ui
+                                .add(egui::TextEdit::singleline(value).hint_text("debug"))
+                                .changed();
+                            if key.contains('=') {
+                                ui.colored_label(egui::Color32::from_rgb(230, 80, 80), "invalid");
+                            }
+                            if ui.button("🗑").on_hover_text("Remove").clicked() {
+                                env_to_remove = Some(index);
+                            }
+                        });
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("Add variable").clicked() {
+                            self.settings.env.push((String::new(), String::new()));
+                            env_changed = true;
+                        }
+                        ui.weak("Applies the next time the command starts.");
+                    });
+                    if let Some(index) = env_to_remove {
+                        self.settings.env.remove(index);
+                        env_changed = true;
+                    }
+                    if env_changed {
+                        self.save_settings();
+                    }
+
+                    if ui
+                        .checkbox(&mut self.settings.show_level_column, "Show level column")
+                        .changed()
+                    {
+                        self.save_settings();
+                    }
+
+                    let mut colorize = self.colorize_levels();
+                    if ui
+                        .checkbox(&mut colorize, "Color severity levels")
+                        .on_hover_text("Defaults to off when NO_COLOR is set")
+                        .changed()
+                    {
+                        self.settings.colorize_levels = Some(colorize);
+                        self.save_settings();
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.label("Theme:");
+                        let previous = self.settings.theme;
+                        egui::ComboBox::from_id_source("theme")
+                            .selected_text(self.settings.theme.display_name())
+                            .show_ui(ui, |ui| {
+                                for theme in [Theme::Dark, Theme::Light, Theme::HighContrast] {
+                                    ui.selectable_value(
+                                        &mut self.settings.theme,
+                                        theme,
+                                        theme.display_name(),
+                                    );
+                                }
+                            });
+                        if self.settings.theme != previous {
+                            ui.ctx().set_visuals(self.settings.theme.visuals());
+                            self.save_settings();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Timestamp format:");
+                        let mut changed = ui
+                            .text_edit_singleline(&mut self.settings.timestamp_display_format)
+                            .on_hover_text("chrono format string, e.g. %H:%M:%S")
+                            .changed();
+                        egui::ComboBox::from_id_source("timestamp_format_presets")
+                            .selected_text("Presets")
+                            .show_ui(ui, |ui| {
+                                for (name, format) in TIMESTAMP_FORMAT_PRESETS {
+                                    if ui.selectable_label(false, name).clicked() {
+                                        self.settings.timestamp_display_format = format.to_string();
+                                        changed = true;
+                                    }
+                                }
+                            });
+                        if !Self::is_valid_time_format(&self.settings.timestamp_display_format) {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(230, 80, 80),
+                                "invalid, using default",
+                            );
+                        }
+                        if changed {
+                            self.save_settings();
+                        }
+                    });
+
+                    if ui
+                        .checkbox(
+                            &mut self.settings.relative_timestamps,
+                            "Show relative timestamps",
+                        )
+                        .on_hover_text("Hover a timestamp to see the absolute time")
+                        .changed()
+                    {
+                        self.save_settings();
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.label("Output encoding:");
+                        let previous = self.settings.encoding;
+                        egui::ComboBox::from_id_source("log_encoding")
+                            .selected_text(self.settings.encoding.display_name())
+                            .show_ui(ui, |ui| {
+                                for encoding in
+                                    [LogEncoding::Auto, LogEncoding::Utf8, LogEncoding::Latin1]
+                                {
+                                    ui.selectable_value(
+                                        &mut self.settings.encoding,
+                                        encoding,
+                                        encoding.display_name(),
+                                    );
+                                }
+                            })
+                            .response
+                            .on_hover_text("Applies from the next restart or file import");
+                        if self.settings.encoding != previous {
+                            self.save_settings();
+                        }
+                    });
+
+                    if ui
+                        .checkbox(&mut self.settings.raw_mode, "Raw mode")
+                        .on_hover_text(
+                            "Keep new lines exactly as emitted, stamped with their arrival time",
+                        )
+                        .changed()
+                    {
+                        self.save_settings();
+                    }
+
+                    if ui
+                        .checkbox(&mut self.settings.skip_blank_lines, "Skip blank lines")
+                        .on_hover_text("Drop empty and whitespace-only lines as they arrive")
+                        .changed()
+                    {
+                        self.save_settings();
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.label("Start followers with the last");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut self.settings.backlog_lines)
+                                    .range(0..=100_000),
+                            )
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                        ui.label("lines");
+                    })
+                    .response
+                    .on_hover_text(
+                        "Adds -n to journalctl -f and --tail to docker/kubectl logs -f; 0 disables",
+                    );
+
+                    ui.horizontal(|ui| {
+                        ui.label("Truncate lines longer than");
+                        let mut kilobytes = self.settings.max_line_bytes / 1024;
+                        let changed = ui
+                            .add(egui::DragValue::new(&mut kilobytes).range(0..=16 * 1024))
+                            .changed();
+                        ui.label("KB");
+                        if changed {
+                            self.settings.max_line_bytes = kilobytes * 1024;
+                            self.save_settings();
+                        }
+                    })
+                    .response
+                    .on_hover_text("Guards the UI against huge single-line dumps; 0 disables");
+
+                    ui.label("Maximum log entries:");
                     if ui
-                        .add(egui::Slider::new(
-                            &mut self.settings.refresh_interval,
-                            100..=5000,
-                        ))
+                        .add(
+                            egui::Slider::new(&mut self.settings.max_log_entries, 1000..=1_000_000)
+                                .logarithmic(true),
+                        )
                         .changed()
                     {
-                        self.settings_changed = true;
+                        self.save_settings();
+                    }
+
+                    ui.horizontal(|ui| {
+                        let mut evict_by_age = self.settings.max_entry_age_secs.is_some();
+                        let mut age_minutes = self.settings.max_entry_age_secs.unwrap_or(3600) / 60;
+                        let toggled = ui
+                            .checkbox(&mut evict_by_age, "Evict entries older than")
+                            .changed();
+                        let age_changed = ui
+                            .add_enabled(
+                                evict_by_age,
+                                egui::DragValue::new(&mut age_minutes)
+                                    .range(1..=10080)
+                                    .suffix(" min"),
+                            )
+                            .changed();
+                        if toggled || age_changed {
+                            self.settings.max_entry_age_secs =
+                                evict_by_age.then_some(age_minutes * 60);
+                            self.save_settings();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Sample: keep 1 in");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut self.settings.sample_every)
+                                    .range(1..=10_000),
+                            )
+                            .on_hover_text("1 keeps every line")
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                    });
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label("Never sample away:");
+                        for level in LEVEL_KEYWORDS {
+                            let mut keep =
+                                self.settings.sample_keep_levels.iter().any(|l| l == level);
+                            if ui.checkbox(&mut keep, level).changed() {
+                                if keep {
+                                    self.settings.sample_keep_levels.push(level.to_string());
+                                } else {
+                                    self.settings.sample_keep_levels.retain(|l| l != level);
+                                }
+                                self.save_settings();
+                            }
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .checkbox(&mut self.settings.striped_rows, "Striped rows")
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                        ui.add_enabled_ui(self.settings.striped_rows, |ui| {
+                            let default_color = ui.visuals().faint_bg_color;
+                            let mut color = self.settings.stripe_color.unwrap_or([
+                                default_color.r(),
+                                default_color.g(),
+                                default_color.b(),
+                            ]);
+                            if ui.color_edit_button_srgb(&mut color).changed() {
+                                self.settings.stripe_color = Some(color);
+                                self.save_settings();
+                            }
+                            if self.settings.stripe_color.is_some()
+                                && ui.button("Default").clicked()
+                            {
+                                self.settings.stripe_color = None;
+                                self.save_settings();
+                            }
+                        });
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Time separators:");
+                        egui::ComboBox::from_id_source("time_separator")
+                            .selected_text(self.settings.time_separator.display_name())
+                            .show_ui(ui, |ui| {
+                                for separator in [
+                                    TimeSeparator::Off,
+                                    TimeSeparator::Hourly,
+                                    TimeSeparator::Daily,
+                                ] {
+                                    if ui
+                                        .selectable_value(
+                                            &mut self.settings.time_separator,
+                                            separator,
+                                            separator.display_name(),
+                                        )
+                                        .changed()
+                                    {
+                                        self.save_settings();
+                                    }
+                                }
+                            });
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Alert when more than");
+                        let threshold_changed = ui
+                            .add(
+                                egui::DragValue::new(&mut self.settings.burst_threshold)
+                                    .range(0..=100_000),
+                            )
+                            .on_hover_text("0 disables the alert")
+                            .changed();
+                        ui.label("errors arrive within");
+                        let window_changed = ui
+                            .add(
+                                egui::DragValue::new(&mut self.settings.burst_window_secs)
+                                    .range(1..=3600)
+                                    .suffix(" s"),
+                            )
+                            .changed();
+                        let notify_changed = ui
+                            .checkbox(&mut self.settings.burst_notify, "Notify")
+                            .changed();
+                        if threshold_changed || window_changed || notify_changed {
+                            self.save_settings();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Cues when a line arrives:");
+                        if ui
+                            .checkbox(&mut self.settings.cues_muted, "Mute all")
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                    });
+                    let mut cues_changed = false;
+                    egui::Grid::new("level_cues").show(ui, |ui| {
+                        for (level, label) in CUE_LEVELS {
+                            let cue = self
+                                .settings
+                                .level_cues
+                                .entry(level.to_string())
+                                .or_default();
+                            ui.label(label);
+                            cues_changed |= ui
+                                .checkbox(&mut cue.sound, "Beep")
+                                .on_hover_text(if cfg!(feature = "sound") {
+                                    "A short tone on the audio device"
+                                } else {
+                                    "The terminal bell; build with the sound feature for a tone"
+                                })
+                                .changed();
+                            cues_changed |= ui.checkbox(&mut cue.flash, "Flash").changed();
+                            ui.end_row();
+                        }
+                    });
+                    if cues_changed {
+                        self.save_settings();
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.label("Warn when no logs arrive for");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut self.settings.stale_after_secs)
+                                    .range(0..=3600)
+                                    .suffix(" s"),
+                            )
+                            .on_hover_text("0 disables the warning")
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .checkbox(
+                                &mut self.settings.auto_restart,
+                                "Restart the command when it exits, after",
+                            )
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                        if ui
+                            .add_enabled(
+                                self.settings.auto_restart,
+                                egui::DragValue::new(&mut self.settings.restart_backoff_secs)
+                                    .range(1..=MAX_RESTART_BACKOFF_SECS)
+                                    .suffix(" s"),
+                            )
+                            .on_hover_text(format!(
+                                "Doubles each time the command exits again quickly, \
+                                 up to {MAX_RESTART_BACKOFF_SECS} s"
+                            ))
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Show a quiet command as running after");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut self.settings.loading_timeout_secs)
+                                    .range(0..=600)
+                                    .suffix(" s"),
+                            )
+                            .on_hover_text("0 keeps the spinner until the first line arrives")
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Clear logs after");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut self.settings.auto_clear_idle_mins)
+                                    .range(0..=1440)
+                                    .suffix(" min"),
+                            )
+                            .on_hover_text(
+                                "0 keeps logs; cleared lines can be restored with Undo Clear",
+                            )
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                        ui.label("without new lines");
+                    });
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        let toggled = ui
+                            .checkbox(
+                                &mut self.settings.first_token_timestamp,
+                                "First token is the timestamp, format:",
+                            )
+                            .on_hover_text(
+                                "Lines whose first token doesn't parse fall back to detection",
+                            )
+                            .changed();
+                        let edited = ui
+                            .add_enabled(
+                                self.settings.first_token_timestamp,
+                                egui::TextEdit::singleline(&mut self.settings.first_token_format)
+                                    .desired_width(180.0),
+                            )
+                            .changed();
+                        if !Self::is_valid_time_format(&self.settings.first_token_format) {
+                            ui.colored_label(egui::Color32::from_rgb(230, 80, 80), "invalid");
+                        }
+                        if toggled || edited {
+                            self.save_settings();
+                        }
+                    });
+
+                    ui.separator();
+                    ui.label("Level pattern (named group `level`, empty uses keywords):");
+                    ui.horizontal(|ui| {
+                        let changed = ui
+                            .add(
+                                egui::TextEdit::singleline(&mut self.settings.level_pattern)
+                                    .hint_text(r"e.g. \]\s*(?P<level>[A-Z]+)\s")
+                                    .desired_width(240.0),
+                            )
+                            .changed();
+                        if changed {
+                            self.compile_level_pattern();
+                            self.save_settings();
+                        }
+                        if !self.settings.level_pattern.trim().is_empty()
+                            && self.level_regex.is_none()
+                        {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(230, 80, 80),
+                                "invalid or missing `level` group",
+                            );
+                        }
+                    });
+                    ui.label("Applies to lines received from now on.");
+
+                    ui.separator();
+                    ui.label("Split into streams by capture group (empty shows one list):");
+                    ui.horizontal(|ui| {
+                        let changed = ui
+                            .add(
+                                egui::TextEdit::singleline(&mut self.settings.split_pattern)
+                                    .hint_text(r"e.g. \[(worker-\d+)\]")
+                                    .desired_width(240.0),
+                            )
+                            .changed();
+                        if changed {
+                            self.compile_split_pattern();
+                            self.save_settings();
+                        }
+                        if !self.settings.split_pattern.trim().is_empty()
+                            && self.split_regex.is_none()
+                        {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(230, 80, 80),
+                                "invalid or missing capture group",
+                            );
+                        }
+                    });
+
+                    ui.separator();
+                    ui.label("Color lines by rule (field query or /regex/, first match wins):");
+                    let mut color_rules_changed = false;
+                    let mut color_rule_to_remove = None;
+                    let mut color_rule_to_raise = None;
+                    for (index, rule) in self.settings.color_rules.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            color_rules_changed |= ui
+                                .add(
+                                    egui::TextEdit::singleline(&mut rule.matcher)
+                                        .hint_text("service=auth or /payment/"),
+                                )
+                                .changed();
+                            color_rules_changed |=
+                                ui.color_edit_button_srgb(&mut rule.color).changed();
+                            if ColorMatcher::parse(&rule.matcher).is_none() {
+                                ui.colored_label(egui::Color32::from_rgb(230, 80, 80), "invalid");
+                            }
+                            if index > 0 && ui.button("⬆").on_hover_text("Check earlier").clicked()
+                            {
+                                color_rule_to_raise = Some(index);
+                            }
+                            if ui.button("🗑").on_hover_text("Remove").clicked() {
+                                color_rule_to_remove = Some(index);
+                            }
+                        });
+                    }
+                    if ui.button("Add color rule").clicked() {
+                        self.settings.color_rules.push(ColorRule {
+                            matcher: String::new(),
+                            color: [100, 150, 230],
+                        });
+                        color_rules_changed = true;
+                    }
+                    if let Some(index) = color_rule_to_raise {
+                        self.settings.color_rules.swap(index - 1, index);
+                        color_rules_changed = true;
                     }
+                    if let Some(index) = color_rule_to_remove {
+                        self.settings.color_rules.remove(index);
+                        color_rules_changed = true;
+                    }
+                    if color_rules_changed {
+                        self.compile_color_rules();
+                        self.save_settings();
+                    }
+
+                    ui.separator();
+                    ui.label("Extract columns with named capture groups:");
+                    let mut rules_changed = false;
+                    let mut rule_to_remove = None;
+                    for (index, rule) in self.settings.extraction_rules.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            rules_changed |= ui
+                                .add(
+                                    egui::TextEdit::singleline(rule)
+                                        .hint_text(r"status=(?P<status>\d+)"),
+                                )
+                                .changed();
+                            if let Err(err) = Regex::new(rule) {
+                                ui.colored_label(egui::Color32::from_rgb(230, 80, 80), "invalid")
+                                    .on_hover_text(err.to_string());
+                            }
+                            if ui.button("🗑").on_hover_text("Remove").clicked() {
+                                rule_to_remove = Some(index);
+                            }
+                        });
+                    }
+                    if ui.button("Add pattern").clicked() {
+                        self.settings.extraction_rules.push(String::new());
+                        rules_changed = true;
+                    }
+                    if let Some(index) = rule_to_remove {
+                        self.settings.extraction_rules.remove(index);
+                        rules_changed = true;
+                    }
+                    if rules_changed {
+                        self.compile_extraction_rules();
+                        self.save_settings();
+                    }
+
+                    ui.separator();
+                    ui.label("Deduplicate by field (JSON or key=value):");
+                    ui.horizontal(|ui| {
+                        let field_changed = ui
+                            .add(
+                                egui::TextEdit::singleline(&mut self.settings.dedup_field)
+                                    .hint_text("e.g. request_id")
+                                    .desired_width(120.0),
+                            )
+                            .changed();
+                        ui.label("once per");
+                        let window_changed = ui
+                            .add(
+                                egui::DragValue::new(&mut self.settings.dedup_window_secs)
+                                    .range(1..=86400)
+                                    .suffix(" s"),
+                            )
+                            .changed();
+                        if field_changed || window_changed {
+                            self.save_settings();
+                        }
+                    });
 
+                    ui.separator();
+                    ui.label("Row action command (from a row's right-click menu):");
                     ui.horizontal(|ui| {
-                        if ui.button("Apply").clicked() && self.settings_changed {
-                            apply_settings = true;
+                        let command_changed = ui
+                            .add(
+                                egui::TextEdit::singleline(&mut self.settings.row_action_command)
+                                    .hint_text("e.g. mytool investigate {content}")
+                                    .desired_width(240.0),
+                            )
+                            .on_hover_text(
+                                "{content}, {timestamp}, {raw}, {level} and parsed field names \
+                                 are filled in from the row",
+                            )
+                            .changed();
+                        let output_changed = ui
+                            .checkbox(&mut self.settings.show_row_action_output, "Show output")
+                            .changed();
+                        if command_changed || output_changed {
+                            self.save_settings();
                         }
+                    });
 
-                        if ui.button("Reset to Default").clicked() {
-                            reset_settings = true;
+                    ui.separator();
+                    ui.label("Keyboard shortcuts (e.g. Ctrl+Shift+K):");
+                    let bound: Vec<(&str, Option<egui::KeyboardShortcut>)> = KEY_ACTIONS
+                        .iter()
+                        .map(|(action, _, default)| {
+                            let combo = self
+                                .settings
+                                .keybindings
+                                .get(*action)
+                                .map_or(*default, String::as_str);
+                            (*action, Self::parse_shortcut(combo))
+                        })
+                        .collect();
+                    let mut bindings_changed = false;
+                    egui::Grid::new("keybindings").show(ui, |ui| {
+                        for (action, label, default) in KEY_ACTIONS {
+                            ui.label(label);
+                            let combo = self
+                                .settings
+                                .keybindings
+                                .entry(action.to_string())
+                                .or_insert_with(|| default.to_string());
+                            bindings_changed |= ui
+                                .add(egui::TextEdit::singleline(combo).desired_width(120.0))
+                                .changed();
+                            let shortcut = bound
+                                .iter()
+                                .find(|(name, _)| *name == action)
+                                .and_then(|(_, shortcut)| *shortcut);
+                            let conflict = shortcut.and_then(|shortcut| {
+                                bound.iter().find(|(name, other)| {
+                                    *name != action && *other == Some(shortcut)
+                                })
+                            });
+                            let red = egui::Color32::from_rgb(230, 80, 80);
+                            if shortcut.is_none() {
+                                ui.colored_label(red, "unknown key");
+                            } else if let Some((other, _)) = conflict {
+                                let other_label = KEY_ACTIONS
+                                    .iter()
+                                    .find(|(name, _, _)| name == other)
+                                    .map_or(*other, |(_, label, _)| *label);
+                                ui.colored_label(red, format!("also bound to {other_label}"));
+                            } else {
+                                ui.label("");
+                            }
+                            ui.end_row();
                         }
                     });
+                    if bindings_changed {
+                        self.save_settings();
+                    }
+
+                    if ui.button("Reset to Default").clicked() {
+                        reset_settings = true;
+                    }
                 });
         }
 
-        if show_favorites {
+        if show_favorites && !self.minimal_mode {
             let mut save_new_favorite = false;
             let mut favorite_to_remove: Option<usize> = None;
-            let mut favorite_to_apply: Option<String> = None;
+            let mut favorite_to_apply: Option<usize> = None;
             let mut save_edit: Option<usize> = None;
             let mut cancel_edit = false;
             let mut start_edit: Option<usize> = None;
+            let mut template_to_use: Option<&str> = None;
 
             egui::Window::new("Favorite Commands")
                 .open(&mut show_favorites)
                 .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Cache last");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut self.settings.favorite_cache_lines)
+                                    .range(0..=5000),
+                            )
+                            .on_hover_text(
+                                "Shown greyed out while a favorite starts; 0 disables the cache",
+                            )
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                        ui.label("lines per favorite");
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Search first above");
+                        if ui
+                            .add(
+                                egui::DragValue::new(
+                                    &mut self.settings.favorites_search_first_above,
+                                )
+                                .range(0..=500),
+                            )
+                            .on_hover_text(
+                                "Only list matches once there are this many; 0 always lists all",
+                            )
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                        ui.label("favorites");
+                    });
+                    ui.separator();
+
+                    ui.heading("Templates");
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_source("command_templates")
+                            .selected_text("Start from a template…")
+                            .show_ui(ui, |ui| {
+                                for (name, command) in COMMAND_TEMPLATES {
+                                    if ui
+                                        .selectable_label(false, name)
+                                        .on_hover_text(command)
+                                        .clicked()
+                                    {
+                                        template_to_use = Some(command);
+                                    }
+                                }
+                            });
+                        ui.weak("Fills the command box; {fields} are asked for on Apply");
+                    });
+
+                    ui.separator();
                     ui.heading("Save Current Command");
                     ui.horizontal(|ui| {
                         ui.label("Name:");
@@ -873,9 +7440,17 @@ impl eframe::App for LogsApp {
                             save_new_favorite = true;
                         }
                     });
+                    ui.checkbox(
+                        &mut self.new_favorite_with_filters,
+                        "Include current filters",
+                    )
+                    .on_hover_text("Restore the level, search and time filters when used");
 
                     ui.separator();
-                    ui.heading("Favorite Commands");
+                    ui.heading(format!(
+                        "Favorite Commands ({})",
+                        self.settings.favorite_commands.len()
+                    ));
 
                     ui.horizontal(|ui| {
                         ui.label("Search:");
@@ -885,8 +7460,21 @@ impl eframe::App for LogsApp {
                         }
                     });
 
+                    let threshold = self.settings.favorites_search_first_above;
+                    let search_first = threshold > 0
+                        && self.settings.favorite_commands.len() > threshold
+                        && self.favorite_search_text.is_empty()
+                        && !self.show_all_favorites;
+
                     if self.settings.favorite_commands.is_empty() {
                         ui.label("No favorite commands saved yet.");
+                    } else if search_first {
+                        ui.horizontal(|ui| {
+                            ui.weak("Type to find a favorite");
+                            if ui.button("Show all").clicked() {
+                                self.show_all_favorites = true;
+                            }
+                        });
                     } else {
                         let filtered_favorites: Vec<(usize, &FavoriteCommand)> = self
                             .settings
@@ -911,7 +7499,7 @@ impl eframe::App for LogsApp {
                                 for (index, favorite) in filtered_favorites {
                                     ui.horizontal(|ui| {
                                         if ui.button("Use").clicked() {
-                                            favorite_to_apply = Some(favorite.command.clone());
+                                            favorite_to_apply = Some(index);
                                         }
 
                                         // Check if this item is being edited
@@ -943,6 +7531,19 @@ impl eframe::App for LogsApp {
                                             // Show read-only with edit button
                                             ui.label(&favorite.name);
                                             ui.label(&favorite.command);
+                                            if let Some(filters) = &favorite.filters {
+                                                let mut levels: Vec<&str> = filters
+                                                    .selected_log_levels
+                                                    .iter()
+                                                    .map(String::as_str)
+                                                    .collect();
+                                                levels.sort_unstable();
+                                                ui.label("🔎").on_hover_text(format!(
+                                                    "Restores filters: levels {}, search \"{}\"",
+                                                    levels.join("/"),
+                                                    filters.search_text
+                                                ));
+                                            }
 
                                             if ui.button("📝").on_hover_text("Edit").clicked() {
                                                 start_edit = Some(index);
@@ -967,10 +7568,17 @@ impl eframe::App for LogsApp {
                     }
                 });
 
+            if let Some(command) = template_to_use {
+                self.source_mut().command = command.to_string();
+            }
+
             if save_new_favorite {
                 let name = self.new_favorite_name.trim().to_string();
-                let command = self.settings.log_command.clone();
-                self.add_favorite_command(name, command);
+                let command = self.source().command.clone();
+                let filters = self
+                    .new_favorite_with_filters
+                    .then(|| self.current_filters());
+                self.add_favorite_command(name, command, filters);
                 self.new_favorite_name.clear();
             }
 
@@ -1017,8 +7625,8 @@ impl eframe::App for LogsApp {
                 self.edit_favorite_command.clear();
             }
 
-            if let Some(command) = favorite_to_apply {
-                self.apply_favorite_command(command);
+            if let Some(index) = favorite_to_apply {
+                self.apply_favorite_command(index);
                 show_favorites = false;
             }
         }
@@ -1026,19 +7634,79 @@ impl eframe::App for LogsApp {
         self.show_settings = show_settings;
         self.show_favorites = show_favorites;
 
-        if apply_settings {
-            self.restart_log_collection();
-            self.settings_changed = false;
-        }
-
         if reset_settings {
             self.settings = Settings::default();
+            self.compile_extraction_rules();
+            self.compile_level_pattern();
+            self.compile_split_pattern();
+            self.compile_color_rules();
+            ctx.set_visuals(self.settings.theme.visuals());
+            let source = &mut self.tabs[self.active_tab];
+            if source
+                .active_favorite
+                .is_some_and(|id| !self.settings.favorite_commands.iter().any(|f| f.id == id))
+            {
+                source.active_favorite = None;
+            }
+            source.command = self.settings.log_command.clone();
+            source.follow_file = self.settings.follow_file;
+            source.ssh = self.settings.ssh.clone();
             self.restart_log_collection();
-            self.settings_changed = false;
+            self.save_settings();
+        }
+
+        self.handle_row_navigation(ctx);
+        self.handle_time_zoom_keys(ctx);
+        if self.minimal_mode {
+            self.show_minimal_mode_overlay(ctx);
         }
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            if self.is_loading {
+        let mut clicked_entry = None;
+        let mut toggle_sort = false;
+        let mut delete_selected = false;
+        let mut row_command_entry = None;
+        let selection_len = self.selected_ids().len();
+        let mut opened_entry = None;
+        let mut pin_toggled = None;
+        let mut jump_to_entry = None;
+        // Set while the filtered lines are still borrowed, stored afterwards
+        let mut visible_count = None;
+
+        self.show_tab_strip(ctx);
+
+        egui::TopBottomPanel::bottom("filter_status").show_animated(
+            ctx,
+            !self.minimal_mode,
+            |ui| {
+                ui.weak(self.filter_summary());
+            },
+        );
+
+        let central_panel = egui::CentralPanel::default().show(ctx, |ui| {
+            let source = self.source();
+            if source.is_loading && !source.cached_preview.is_empty() {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Loading… showing lines cached from the last run");
+                });
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for line in &source.cached_preview {
+                            ui.weak(line);
+                        }
+                    });
+            } else if let Some(regex) = &self.split_regex
+                && !source.is_loading
+            {
+                let filtered_logs = self.filtered_logs();
+                visible_count = Some(filtered_logs.len());
+                if let Some(id) = self.show_split_streams(ui, regex, &filtered_logs) {
+                    clicked_entry = Some((id, ui.input(|i| i.modifiers)));
+                }
+            } else if source.is_loading {
                 // Show loading spinner when waiting for command output
                 ui.with_layout(
                     egui::Layout::centered_and_justified(egui::Direction::TopDown),
@@ -1081,67 +7749,951 @@ impl eframe::App for LogsApp {
                         }
 
                         ui.add_space(20.0);
-                        ui.label("Loading logs...");
-                        ui.label(format!("Running: {}", self.settings.log_command));
+                        let elapsed = source
+                            .collection_started
+                            .map(|started| started.elapsed().as_secs())
+                            .unwrap_or(0);
+                        let bytes = source.bytes_received.load(Ordering::Relaxed);
+                        ui.label(format!(
+                            "Loading logs... {elapsed}s elapsed, {bytes} bytes received"
+                        ));
+                        if bytes == 0 && elapsed >= LOADING_HINT_AFTER_SECS {
+                            ui.weak("Still waiting — the command may produce no output");
+                        }
+                        // Wrapped and selectable so a long command can be checked and copied
+                        ui.add(
+                            egui::Label::new(format!(
+                                "Running: {}",
+                                source
+                                    .running_command
+                                    .clone()
+                                    .unwrap_or_else(|| Self::effective_command(&source.command))
+                            ))
+                            .wrap()
+                            .selectable(true),
+                        );
                     },
                 );
             } else {
                 // Show normal log display
                 let filtered_logs = self.filtered_logs();
+                // Read by the status line, which is laid out before this panel
+                visible_count = Some(filtered_logs.len());
+
+                if let Some(error) = &source.last_error {
+                    ui.colored_label(egui::Color32::from_rgb(230, 80, 80), error);
+                    if let Some(command) = &source.running_command {
+                        ui.add(
+                            egui::Label::new(
+                                egui::RichText::new(format!("Command: {command}")).monospace(),
+                            )
+                            .wrap()
+                            .selectable(true),
+                        );
+                    }
+                    ui.separator();
+                }
+
+                if source.logs.is_empty() && source.collection_state == CollectionState::Tailing {
+                    ui.weak("No logs yet — the command is running but hasn't printed anything");
+                }
+
+                let extraction_columns = self.extraction_columns();
+                let term_highlighters = self.search_term_highlighters();
+                // Raw mode shows just the arrival time and the verbatim line
+                let show_level_column = self.settings.show_level_column && !self.settings.raw_mode;
+                let colorize_levels = self.colorize_levels();
+                // Prefix markers stand in for color when it is missing or unreliable
+                let show_markers = !colorize_levels || self.settings.theme == Theme::HighContrast;
+
+                if !self.pinned_entries.is_empty() {
+                    ui.horizontal(|ui| {
+                        ui.strong("📌 Pinned");
+                    });
+                    egui::ScrollArea::vertical()
+                        .id_source("pinned_strip")
+                        .max_height(120.0)
+                        .show(ui, |ui| {
+                            for entry in &self.pinned_entries {
+                                ui.horizontal(|ui| {
+                                    if ui.small_button("✖").on_hover_text("Unpin").clicked() {
+                                        pin_toggled = Some(entry.id);
+                                    }
+                                    if ui
+                                        .link(&entry.timestamp)
+                                        .on_hover_text("Jump to this line")
+                                        .clicked()
+                                    {
+                                        jump_to_entry = Some(entry.id);
+                                    }
+                                    if let Some(level) = entry.level {
+                                        ui.label(Self::level_chip(
+                                            level,
+                                            colorize_levels,
+                                            show_markers,
+                                        ));
+                                    }
+                                    ui.add(egui::Label::new(&entry.content).truncate());
+                                });
+                            }
+                        });
+                    ui.separator();
+                }
+
+                if let Some(id) = Self::show_severity_minimap(ui, &filtered_logs) {
+                    jump_to_entry = Some(id);
+                }
 
                 egui::ScrollArea::vertical()
                     .auto_shrink([false, false])
                     .stick_to_bottom(self.auto_scroll)
                     .show(ui, |ui| {
+                        if let Some([r, g, b]) = self.settings.stripe_color {
+                            ui.visuals_mut().faint_bg_color = egui::Color32::from_rgb(r, g, b);
+                        }
+
                         egui::Grid::new("log_grid")
-                            .striped(true)
+                            .striped(self.settings.striped_rows)
                             .spacing([10.0, 4.0])
                             .show(ui, |ui| {
+                                let mut previous_bucket = None;
+                                let now = Local::now().naive_local();
+                                let display_format = self.timestamp_display_format();
+                                let column_count =
+                                    2 + usize::from(show_level_column) + extraction_columns.len();
+
                                 // Table headers
-                                ui.strong("Timestamp");
+                                let timestamp_header = if self.settings.sort_by_timestamp {
+                                    "Timestamp ▲"
+                                } else {
+                                    "Timestamp"
+                                };
+                                if ui
+                                    .add(
+                                        egui::Label::new(
+                                            egui::RichText::new(timestamp_header).strong(),
+                                        )
+                                        .sense(egui::Sense::click()),
+                                    )
+                                    .on_hover_text(
+                                        "Click to toggle sorting by time instead of arrival",
+                                    )
+                                    .clicked()
+                                {
+                                    toggle_sort = true;
+                                }
+                                if show_level_column {
+                                    ui.strong("Level");
+                                }
+                                for column in &extraction_columns {
+                                    ui.strong(column);
+                                }
                                 ui.strong("Log Content");
                                 ui.end_row();
-
-                                // Add separator line
-                                ui.separator();
-                                ui.separator();
+                                for _ in 0..column_count {
+                                    ui.separator();
+                                }
                                 ui.end_row();
 
                                 // Log entries
                                 for log_entry in filtered_logs {
+                                    if let Some(bucket) = Self::parse_time_input(
+                                        &log_entry.timestamp,
+                                    )
+                                    .and_then(|time| self.settings.time_separator.bucket(time))
+                                    {
+                                        if previous_bucket
+                                            .is_some_and(|previous| previous != bucket)
+                                        {
+                                            ui.label(
+                                                egui::RichText::new(format!(
+                                                    "── {} ──",
+                                                    self.settings.time_separator.label(bucket)
+                                                ))
+                                                .weak()
+                                                .small(),
+                                            );
+                                            for _ in 1..column_count {
+                                                ui.label("");
+                                            }
+                                            ui.end_row();
+                                        }
+                                        previous_bucket = Some(bucket);
+                                    }
+
+                                    let is_selected = self.selected_entry == Some(log_entry.id)
+                                        || self.marked_entries.contains(&log_entry.id);
+                                    let mut row_action = None;
+                                    let selection_color = ui.visuals().selection.bg_fill;
+                                    let row_color = self
+                                        .rule_color(log_entry)
+                                        .or(log_entry.is_stderr.then_some(STDERR_TEXT_COLOR));
+                                    let highlight = |text: &str| {
+                                        let mut text = egui::RichText::new(text);
+                                        if let Some(color) = row_color {
+                                            text = text.color(color);
+                                        }
+                                        if is_selected {
+                                            text.background_color(selection_color)
+                                        } else {
+                                            text
+                                        }
+                                    };
+
                                     ui.with_layout(
                                         egui::Layout::left_to_right(egui::Align::TOP),
                                         |ui| {
-                                            ui.add_sized(
+                                            let timestamp_text = self.display_timestamp(
+                                                &log_entry.timestamp,
+                                                now,
+                                                display_format,
+                                            );
+                                            let mut response = ui.add_sized(
                                                 [180.0, ui.available_height()],
-                                                egui::Label::new(&log_entry.timestamp),
+                                                egui::Label::new(highlight(&timestamp_text))
+                                                    .sense(egui::Sense::click()),
                                             );
+                                            if timestamp_text != log_entry.timestamp {
+                                                response =
+                                                    response.on_hover_text(&log_entry.timestamp);
+                                            }
+                                            if response.clicked() {
+                                                clicked_entry =
+                                                    Some((log_entry.id, ui.input(|i| i.modifiers)));
+                                            }
+                                            if response.double_clicked() {
+                                                opened_entry = Some(log_entry.id);
+                                            }
+                                            if is_selected && self.scroll_to_selected {
+                                                response.scroll_to_me(Some(egui::Align::Center));
+                                            }
+                                            response.context_menu(|ui| {
+                                                row_action = self.row_context_menu(
+                                                    ui,
+                                                    log_entry,
+                                                    selection_len,
+                                                );
+                                            });
                                         },
                                     );
+                                    if show_level_column {
+                                        ui.with_layout(
+                                            egui::Layout::left_to_right(egui::Align::TOP),
+                                            |ui| {
+                                                let chip = log_entry.level.map(|level| {
+                                                    Self::level_chip(
+                                                        level,
+                                                        colorize_levels,
+                                                        show_markers,
+                                                    )
+                                                });
+                                                ui.add_sized(
+                                                    [70.0, ui.available_height()],
+                                                    egui::Label::new(chip.unwrap_or_default()),
+                                                );
+                                            },
+                                        );
+                                    }
+                                    for column in &extraction_columns {
+                                        ui.with_layout(
+                                            egui::Layout::left_to_right(egui::Align::TOP),
+                                            |ui| {
+                                                ui.add_sized(
+                                                    [100.0, ui.available_height()],
+                                                    egui::Label::new(
+                                                        log_entry.field(column).unwrap_or(""),
+                                                    )
+                                                    .truncate(),
+                                                );
+                                            },
+                                        );
+                                    }
                                     ui.with_layout(
                                         egui::Layout::left_to_right(egui::Align::TOP),
                                         |ui| {
-                                            ui.label(&log_entry.content);
+                                            let content = &log_entry.content;
+                                            let links = Self::url_ranges(content);
+                                            let mut job = Self::highlight_terms(
+                                                content,
+                                                &term_highlighters,
+                                                &links,
+                                                is_selected.then_some(selection_color),
+                                                row_color,
+                                                ui.style(),
+                                            );
+                                            job.wrap.max_width = ui.available_width();
+                                            let galley = ui.fonts(|fonts| fonts.layout_job(job));
+                                            let response = ui.add(
+                                                egui::Label::new(galley.clone())
+                                                    .sense(egui::Sense::click()),
+                                            );
+                                            // Links are part of the text, so find the one
+                                            // under the pointer from the laid out galley
+                                            let link_at = |pos: egui::Pos2| {
+                                                let index = galley
+                                                    .cursor_from_pos(pos - response.rect.min)
+                                                    .ccursor
+                                                    .index;
+                                                let byte = content
+                                                    .char_indices()
+                                                    .nth(index)
+                                                    .map_or(content.len(), |(byte, _)| byte);
+                                                links
+                                                    .iter()
+                                                    .find(|link| link.contains(&byte))
+                                                    .map(|link| &content[link.clone()])
+                                            };
+                                            if response.hover_pos().and_then(link_at).is_some() {
+                                                ui.ctx().set_cursor_icon(
+                                                    egui::CursorIcon::PointingHand,
+                                                );
+                                            }
+                                            if response.clicked()
+                                                && let Some(url) = response
+                                                    .interact_pointer_pos()
+                                                    .and_then(link_at)
+                                            {
+                                                ui.ctx().open_url(egui::OpenUrl::new_tab(url));
+                                            }
+                                            response.context_menu(|ui| {
+                                                row_action = self.row_context_menu(
+                                                    ui,
+                                                    log_entry,
+                                                    selection_len,
+                                                );
+                                            });
                                         },
                                     );
                                     ui.end_row();
+
+                                    match row_action {
+                                        Some(RowAction::TogglePin) => {
+                                            pin_toggled = Some(log_entry.id)
+                                        }
+                                        Some(RowAction::OpenDetails) => {
+                                            opened_entry = Some(log_entry.id)
+                                        }
+                                        Some(RowAction::DeleteSelected) => delete_selected = true,
+                                        Some(RowAction::RunCommand) => {
+                                            row_command_entry = Some(log_entry.id)
+                                        }
+                                        None => {}
+                                    }
                                 }
                             });
                     });
             }
         });
+        self.logs_panel_rect = Some(central_panel.response.rect);
+        if let Some(count) = visible_count {
+            self.source_mut().visible_count = count;
+        }
+
+        self.scroll_to_selected = false;
+        if toggle_sort {
+            self.settings.sort_by_timestamp = !self.settings.sort_by_timestamp;
+            self.save_settings();
+        }
+        if let Some((id, modifiers)) = clicked_entry {
+            self.click_entry(id, modifiers);
+        }
+        if delete_selected {
+            self.delete_selected_entries();
+        }
+        if let Some(id) = row_command_entry {
+            self.run_row_action(id);
+        }
+        self.poll_row_action_results();
+        if let Some(id) = opened_entry {
+            self.detail_entry = Some(id);
+        }
+        if let Some(id) = pin_toggled {
+            self.toggle_pin(id);
+        }
+        if let Some(id) = jump_to_entry {
+            self.selected_entry = Some(id);
+            self.scroll_to_selected = true;
+            self.auto_scroll = false;
+        }
+        self.show_detail_window(ctx);
+        self.show_placeholder_window(ctx);
+        self.show_glob_window(ctx);
+        self.show_diff_window(ctx);
+        self.show_syslog_window(ctx);
+        self.show_reload_restart_prompt(ctx);
+        self.show_journalctl_window(ctx);
+        self.show_group_by_window(ctx);
+        self.show_docker_picker(ctx);
+        self.show_pod_picker(ctx);
+
+        self.show_toasts(ctx);
+        self.draw_cue_flash(ctx);
+        self.poll_file_dialog();
+        self.handle_screenshot(ctx);
+    }
+}
+
+impl eframe::App for LogsApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.run_frame(ctx);
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_settings();
+        for tab in 0..self.tabs.len() {
+            self.save_favorite_cache(tab);
+        }
+        // Reap the commands here; a background thread wouldn't outlive the app
+        for source in &mut self.tabs {
+            if let Some(child) = source.take_child() {
+                Self::terminate_child(child);
+            }
+            source.stop();
+        }
     }
 }
 
 fn main() -> Result<(), eframe::Error> {
+    // `myserver | logs` pipes lines in; `-` reads stdin whatever it is
+    let read_stdin = std::env::args().skip(1).any(|arg| arg == "-") || LogsApp::stdin_is_piped();
+    let window_size = LogsApp::load_settings()
+        .window_size
+        .unwrap_or([1200.0, 800.0]);
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([1200.0, 800.0])
+            .with_inner_size(window_size)
             .with_title("Logs Viewer"),
         ..Default::default()
     };
 
-    let app = LogsApp::default();
+    eframe::run_native(
+        "Logs Viewer",
+        options,
+        Box::new(move |cc| Ok(Box::new(LogsApp::new(cc, read_stdin)))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SYSLOG_FORMAT: &str = "%b %d %H:%M:%S";
+
+    fn datetime(text: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn split_streams_fold_keys_past_the_cap_into_other() {
+        let mut app = LogsApp::with_settings(Settings::default());
+        for (index, service) in ["a", "b", "c", "d", "e", "f", "g", "a"].iter().enumerate() {
+            app.add_log_entry(0, format!("service={service} line {index}"), false);
+        }
+        app.add_log_entry(0, "no service here".to_string(), false);
+        let regex = Regex::new(r"service=(\w+)").unwrap();
+        let logs: Vec<&LogEntry> = app.source().logs.iter().collect();
+
+        let streams = LogsApp::split_streams(&regex, &logs);
+        let names: Vec<&str> = streams.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c", "d", "e", "other"]);
+        assert_eq!(streams[0].1.len(), 2);
+        let other: Vec<&str> = streams[5]
+            .1
+            .iter()
+            .map(|entry| entry.content.as_str())
+            .collect();
+        assert_eq!(
+            other,
+            ["service=f line 5", "service=g line 6", "no service here"]
+        );
+    }
+
+    #[test]
+    fn group_filter_quotes_values_with_spaces_and_quotes() {
+        let mut app = LogsApp::with_settings(Settings::default());
+        app.add_log_entry(
+            0,
+            r#"{"msg":"disk \"sda\" full","level":"warn"}"#.to_string(),
+            false,
+        );
+        app.add_log_entry(0, r#"{"msg":"disk","level":"warn"}"#.to_string(), false);
+
+        app.filter_to_group(Some("msg".to_string()), r#"disk "sda" full"#.to_string());
+        assert_eq!(app.search_text, r#"msg="disk \"sda\" full""#);
+        let logs = &app.source().logs;
+        assert_eq!(
+            LogsApp::match_field_query(&logs[0], &app.search_text),
+            Some(true)
+        );
+        assert_eq!(
+            LogsApp::match_field_query(&logs[1], &app.search_text),
+            Some(false)
+        );
+
+        app.filter_to_group(Some("level".to_string()), "warn".to_string());
+        assert_eq!(app.search_text, "level=warn");
+    }
+
+    #[test]
+    fn syslog_octet_counted_frames_are_split_by_length() {
+        let mut pending = b"9 <34>1 a\nb12 <13>Oct 11 x\n5 <1>".to_vec();
+        assert_eq!(
+            LogsApp::take_syslog_frame(&mut pending).unwrap().as_deref(),
+            Some("<34>1 a\nb")
+        );
+        assert_eq!(
+            LogsApp::take_syslog_frame(&mut pending).unwrap().as_deref(),
+            Some("<13>Oct 11 x")
+        );
+        assert_eq!(
+            LogsApp::take_syslog_frame(&mut pending).unwrap().as_deref(),
+            Some("\n")
+        );
+        // The rest of the last frame hasn't arrived yet
+        assert_eq!(LogsApp::take_syslog_frame(&mut pending).unwrap(), None);
+        pending.extend_from_slice(b"ok");
+        assert_eq!(
+            LogsApp::take_syslog_frame(&mut pending).unwrap().as_deref(),
+            Some("<1>ok")
+        );
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn syslog_octet_counted_frames_over_the_limit_are_rejected() {
+        let mut pending = b"999999999 <13>Oct 11 22:14:15 host app: x".to_vec();
+        assert!(LogsApp::take_syslog_frame(&mut pending).is_err());
+        let mut pending = format!("{} <13>", SYSLOG_MAX_FRAME_BYTES + 1).into_bytes();
+        assert!(LogsApp::take_syslog_frame(&mut pending).is_err());
+    }
+
+    #[test]
+    fn syslog_newline_frames_wait_for_the_newline() {
+        let mut pending = b"<13>Oct 11 22:14:15 host app: one\n<13>Oct 11 22:14:16".to_vec();
+        assert_eq!(
+            LogsApp::take_syslog_frame(&mut pending).unwrap().as_deref(),
+            Some("<13>Oct 11 22:14:15 host app: one\n")
+        );
+        assert_eq!(LogsApp::take_syslog_frame(&mut pending).unwrap(), None);
+        assert_eq!(pending, b"<13>Oct 11 22:14:16");
+    }
+
+    #[test]
+    fn syslog_december_read_in_january_uses_previous_year() {
+        let now = datetime("2026-01-03 10:00:00");
+        let parsed = LogsApp::parse_syslog_timestamp("Dec 31 23:59:59", SYSLOG_FORMAT, now);
+        assert_eq!(parsed, Some(datetime("2025-12-31 23:59:59")));
+    }
+
+    #[test]
+    fn syslog_january_read_in_january_uses_current_year() {
+        let now = datetime("2026-01-01 00:05:00");
+        let parsed = LogsApp::parse_syslog_timestamp("Jan  1 00:01:00", SYSLOG_FORMAT, now);
+        assert_eq!(parsed, Some(datetime("2026-01-01 00:01:00")));
+    }
+
+    #[test]
+    fn syslog_december_read_in_december_uses_current_year() {
+        let now = datetime("2025-12-31 23:00:00");
+        let parsed = LogsApp::parse_syslog_timestamp("Dec 31 22:00:00", SYSLOG_FORMAT, now);
+        assert_eq!(parsed, Some(datetime("2025-12-31 22:00:00")));
+    }
+
+    #[test]
+    fn syslog_leap_day_is_resolved_to_a_leap_year_only() {
+        let now = datetime("2024-03-01 00:00:00");
+        let parsed = LogsApp::parse_syslog_timestamp("Feb 29 12:00:00", SYSLOG_FORMAT, now);
+        assert_eq!(parsed, Some(datetime("2024-02-29 12:00:00")));
+
+        let now = datetime("2025-03-01 00:00:00");
+        assert_eq!(
+            LogsApp::parse_syslog_timestamp("Feb 29 12:00:00", SYSLOG_FORMAT, now),
+            None
+        );
+    }
+
+    fn extract(line: &str) -> (Option<String>, String) {
+        LogsApp::extract_timestamp_from_log(line)
+    }
+
+    #[test]
+    fn full_datetime_beats_earlier_bare_time() {
+        let (timestamp, content) = extract("req at 12:00:00 completed 2025-09-15 14:30:00");
+        assert_eq!(timestamp.as_deref(), Some("2025-09-15 14:30:00"));
+        assert_eq!(content, "req at 12:00:00 completed");
+    }
+
+    #[test]
+    fn leftmost_of_two_datetimes_wins() {
+        let (timestamp, content) = extract("2025-09-15 14:30:00 retry of 2025-09-14 10:00:00");
+        assert_eq!(timestamp.as_deref(), Some("2025-09-15 14:30:00"));
+        assert_eq!(content, "retry of 2025-09-14 10:00:00");
+    }
+
+    #[test]
+    fn longest_match_at_the_same_position_wins() {
+        let (timestamp, content) = extract("2025-09-15 14:30:00.123 done");
+        assert_eq!(timestamp.as_deref(), Some("2025-09-15 14:30:00"));
+        assert_eq!(content, "done");
+    }
+
+    #[test]
+    fn datetime_beats_earlier_epoch_like_number() {
+        let (timestamp, content) = extract("job 1726401000 started 2025-09-15 14:30:00");
+        assert_eq!(timestamp.as_deref(), Some("2025-09-15 14:30:00"));
+        assert_eq!(content, "job 1726401000 started");
+    }
+
+    #[test]
+    fn only_the_chosen_occurrence_is_removed() {
+        let (timestamp, content) = extract("2025-09-15 14:30:00 echo 2025-09-15 14:30:00");
+        assert_eq!(timestamp.as_deref(), Some("2025-09-15 14:30:00"));
+        assert_eq!(content, "echo 2025-09-15 14:30:00");
+    }
+
+    #[test]
+    fn line_without_timestamp_is_untouched() {
+        assert_eq!(extract("no time here"), (None, "no time here".to_string()));
+    }
+
+    /// Live threads and open file descriptors of this process
+    #[cfg(target_os = "linux")]
+    fn process_resources() -> (usize, usize) {
+        let count = |dir| {
+            fs::read_dir(dir)
+                .map(|entries| entries.count())
+                .unwrap_or(0)
+        };
+        (count("/proc/self/task"), count("/proc/self/fd"))
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn restarting_collection_leaves_no_threads_or_descriptors_behind() {
+        let (threads, descriptors) = process_resources();
+
+        let mut app = LogsApp::with_settings(Settings::default());
+        app.source_mut().command = "sleep 30".to_string();
+        app.settings.backlog_lines = 0;
+        app.start_log_collection(0);
+        for _ in 0..100 {
+            app.restart_log_collection();
+        }
+        app.stop_log_collection();
+
+        // Reader threads exit on their own once their child is gone; allow a
+        // little slack for tests running alongside this one
+        let deadline = Instant::now() + std::time::Duration::from_secs(5);
+        loop {
+            let (now_threads, now_descriptors) = process_resources();
+            if now_threads <= threads + 8 && now_descriptors <= descriptors + 4 {
+                break;
+            }
+            assert!(
+                Instant::now() < deadline,
+                "leaked {} threads and {} descriptors",
+                now_threads.saturating_sub(threads),
+                now_descriptors.saturating_sub(descriptors)
+            );
+            thread::sleep(std::time::Duration::from_millis(50));
+        }
+    }
+
+    /// Pid of the command once the collection thread has published it
+    #[cfg(target_os = "linux")]
+    fn spawned_pid(app: &LogsApp) -> u32 {
+        let shared = app.source().log_child.clone().expect("collection started");
+        let deadline = Instant::now() + std::time::Duration::from_secs(5);
+        loop {
+            if let Some(child) = shared.lock().unwrap().as_ref() {
+                return child.id();
+            }
+            assert!(Instant::now() < deadline, "command never spawned");
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn restarting_collection_leaves_no_orphaned_processes() {
+        let mut app = LogsApp::with_settings(Settings::default());
+        app.source_mut().command = "sleep 30".to_string();
+        app.settings.backlog_lines = 0;
+        app.start_log_collection(0);
+        let mut pids = vec![spawned_pid(&app)];
+        for _ in 0..5 {
+            app.restart_log_collection();
+            pids.push(spawned_pid(&app));
+        }
+        app.stop_log_collection();
+
+        // Terminated children are reaped, so their /proc entries disappear
+        let deadline = Instant::now() + std::time::Duration::from_secs(5);
+        loop {
+            let alive: Vec<u32> = pids
+                .iter()
+                .copied()
+                .filter(|pid| Path::new(&format!("/proc/{pid}")).exists())
+                .collect();
+            if alive.is_empty() {
+                break;
+            }
+            assert!(Instant::now() < deadline, "still running: {alive:?}");
+            thread::sleep(std::time::Duration::from_millis(50));
+        }
+    }
+
+    #[test]
+    fn double_and_single_quotes_keep_phrases_together() {
+        assert_eq!(
+            LogsApp::command_args(r#"grep "foo bar" 'a  b' file.log"#).unwrap(),
+            ["grep", "foo bar", "a  b", "file.log"]
+        );
+    }
+
+    #[test]
+    fn escaped_spaces_stay_in_the_argument() {
+        assert_eq!(
+            LogsApp::command_args(r"tail -f /var/log/my\ app.log").unwrap(),
+            ["tail", "-f", "/var/log/my app.log"]
+        );
+    }
+
+    #[test]
+    fn empty_or_unbalanced_commands_are_rejected() {
+        assert!(LogsApp::command_args("").is_err());
+        assert!(LogsApp::command_args("   ").is_err());
+        assert!(LogsApp::command_args(r#"grep "foo bar"#).is_err());
+    }
+
+    #[test]
+    fn pasted_multiline_commands_run_as_one_line() {
+        assert_eq!(
+            LogsApp::effective_command("kubectl logs -f \\\n    deploy/api \\\n\n  -n prod\n"),
+            "kubectl logs -f deploy/api -n prod"
+        );
+    }
+
+    #[test]
+    fn url_ranges_leave_out_trailing_punctuation() {
+        let text = "see https://example.com/a?b=1. or (http://x.io/path), not https://";
+        let urls: Vec<&str> = LogsApp::url_ranges(text)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect();
+        assert_eq!(urls, ["https://example.com/a?b=1", "http://x.io/path"]);
+    }
+
+    #[test]
+    fn journalctl_command_quotes_values_with_spaces() {
+        let form = JournalctlForm {
+            unit: "my app.service".to_string(),
+            priority: Some(3),
+            use_time_filter: true,
+            grep: "connection refused".to_string(),
+            follow: false,
+        };
+        let range = (
+            datetime("2025-09-15 14:30:00"),
+            datetime("2025-09-15 15:00:00"),
+        );
+        let command = LogsApp::journalctl_command(&form, Some(range)).unwrap();
+        assert_eq!(
+            LogsApp::command_args(&command).unwrap(),
+            [
+                "journalctl",
+                "-u",
+                "my app.service",
+                "-p",
+                "err",
+                "--since=2025-09-15 14:30:00",
+                "--until=2025-09-15 15:00:00",
+                "--grep=connection refused"
+            ]
+        );
+    }
 
-    eframe::run_native("Logs Viewer", options, Box::new(|_cc| Ok(Box::new(app))))
+    #[test]
+    fn backlog_keeps_quoted_arguments_together() {
+        let command = LogsApp::with_backlog(r#"journalctl -f --grep "connection refused""#, 50);
+        assert_eq!(
+            LogsApp::command_args(&command.unwrap()).unwrap(),
+            [
+                "journalctl",
+                "-n",
+                "50",
+                "-f",
+                "--grep",
+                "connection refused"
+            ]
+        );
+        assert_eq!(
+            LogsApp::with_backlog("docker logs --follow web", 50).as_deref(),
+            Some("docker logs --tail 50 --follow web")
+        );
+        assert_eq!(LogsApp::with_backlog("journalctl -u sshd", 50), None);
+    }
+
+    #[test]
+    fn docker_ps_lines_with_odd_spacing_are_parsed() {
+        let output = "  abc123   web-1  \n\n def456\tdb\n   \n";
+        assert_eq!(
+            LogsApp::parse_docker_ps(output),
+            [
+                ("abc123".to_string(), "web-1".to_string()),
+                ("def456".to_string(), "db".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn kubectl_logs_command_puts_namespace_before_container() {
+        assert_eq!(
+            LogsApp::kubectl_logs_command("api-7f9", " prod ", Some("sidecar")),
+            "kubectl logs -f api-7f9 -n prod -c sidecar"
+        );
+        assert_eq!(
+            LogsApp::kubectl_logs_command("api-7f9", "", None),
+            "kubectl logs -f api-7f9"
+        );
+    }
+
+    fn ssh_target(user: &str, host: &str) -> SshTarget {
+        SshTarget {
+            host: host.to_string(),
+            user: user.to_string(),
+            port: 2222,
+        }
+    }
+
+    #[test]
+    fn ssh_rejects_destinations_that_look_like_options() {
+        for target in [
+            ssh_target("", "-oProxyCommand=touch /tmp/owned"),
+            ssh_target("-lroot", "example.com"),
+        ] {
+            assert!(LogsApp::ssh_process(&target, "tail -f app.log", &[], None).is_err());
+        }
+    }
+
+    #[test]
+    fn ssh_sends_the_remote_command_as_one_final_argument() {
+        let env = [("LOG_LEVEL".to_string(), "debug and more".to_string())];
+        let process = LogsApp::ssh_process(
+            &ssh_target("deploy", "example.com"),
+            " tail -f app.log ",
+            &env,
+            Some(Path::new("/srv/my app")),
+        )
+        .unwrap();
+        let args: Vec<String> = process
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(args[args.len() - 2], "deploy@example.com");
+        assert_eq!(
+            shlex::split(&args[args.len() - 1]).unwrap(),
+            [
+                "cd",
+                "/srv/my app",
+                "&&",
+                "export",
+                "LOG_LEVEL=debug and more",
+                "&&",
+                "tail",
+                "-f",
+                "app.log"
+            ]
+        );
+    }
+
+    #[test]
+    fn a_frame_takes_in_at_most_max_lines_per_frame() {
+        let mut app = LogsApp::with_settings(Settings::default());
+        let (tx, rx) = mpsc::channel();
+        app.source_mut().log_receiver = Some(rx);
+        app.source_mut().collection_state = CollectionState::Tailing;
+        let total = MAX_LINES_PER_FRAME * 2 + 10;
+        for line in 0..total {
+            tx.send(CollectorMessage::Line(format!("INFO line {line}")))
+                .unwrap();
+        }
+
+        let ctx = egui::Context::default();
+        app.drain_collector(0, &ctx);
+        assert_eq!(app.source().logs.len(), MAX_LINES_PER_FRAME);
+        app.drain_collector(0, &ctx);
+        assert_eq!(app.source().logs.len(), MAX_LINES_PER_FRAME * 2);
+        app.drain_collector(0, &ctx);
+        assert_eq!(app.source().logs.len(), total);
+    }
+
+    #[test]
+    fn search_box_keeps_focus_when_toolbar_widgets_before_it_change() {
+        let mut app = LogsApp::with_settings(Settings::default());
+        let ctx = egui::Context::default();
+        let search_id = egui::Id::new(SEARCH_INPUT_ID);
+        ctx.memory_mut(|memory| memory.request_focus(search_id));
+
+        for (frame, character) in "abcd".chars().enumerate() {
+            // The Reconnect button only shows for remote sources
+            app.source_mut().ssh = (frame % 2 == 1).then(|| ssh_target("", "example.com"));
+            let input = egui::RawInput {
+                screen_rect: Some(egui::Rect::from_min_size(
+                    egui::Pos2::ZERO,
+                    egui::vec2(1600.0, 900.0),
+                )),
+                events: vec![egui::Event::Text(character.to_string())],
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| app.run_frame(ctx));
+            assert!(ctx.memory(|memory| memory.has_focus(search_id)));
+        }
+
+        assert_eq!(app.search_text, "abcd");
+    }
+
+    #[test]
+    fn typing_into_search_keeps_every_keystroke_under_heavy_log_flow() {
+        let mut app = LogsApp::with_settings(Settings::default());
+        let (tx, rx) = mpsc::channel();
+        app.source_mut().log_receiver = Some(rx);
+        app.source_mut().collection_state = CollectionState::Tailing;
+
+        let ctx = egui::Context::default();
+        // Focused once; the box has to keep it through every later frame
+        ctx.memory_mut(|memory| memory.request_focus(egui::Id::new(SEARCH_INPUT_ID)));
+        let typed = "needle-42";
+        let mut line = 0;
+        for (frame, character) in std::iter::once(None)
+            .chain(typed.chars().map(Some))
+            .enumerate()
+        {
+            // About 10k lines per second at 60 frames per second, plus a burst
+            let burst = if frame == 3 { 10_000 } else { 170 };
+            for _ in 0..burst {
+                line += 1;
+                tx.send(CollectorMessage::Line(format!(
+                    "2025-09-15 14:30:00 INFO line {line}"
+                )))
+                .unwrap();
+            }
+
+            let mut input = egui::RawInput {
+                screen_rect: Some(egui::Rect::from_min_size(
+                    egui::Pos2::ZERO,
+                    egui::vec2(1600.0, 900.0),
+                )),
+                ..Default::default()
+            };
+            if let Some(character) = character {
+                input.events.push(egui::Event::Text(character.to_string()));
+            }
+            let _ = ctx.run(input, |ctx| app.run_frame(ctx));
+        }
+
+        assert_eq!(app.search_text, typed);
+        assert!(!app.source().logs.is_empty());
+    }
 }